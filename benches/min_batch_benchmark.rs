@@ -1,5 +1,5 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
-use futures::stream::{self, Stream};
+use futures::stream::{self, Stream, StreamExt};
 use min_batch::ext::MinBatchExt;
 use tokio::runtime::Runtime;
 
@@ -7,6 +7,27 @@ async fn batch(stream: impl Stream<Item = i32>) {
     let _ = stream.min_batch(1000, |i| *i as usize);
 }
 
+async fn batch_pinned(stream: impl Stream<Item = i32> + Unpin) {
+    stream
+        .min_batch(1000, |i| *i as usize % 10)
+        .for_each(|_| async {})
+        .await;
+}
+
+async fn batch_unpin(stream: impl Stream<Item = i32> + Unpin) {
+    stream
+        .min_batch_unpin(1000, |i| *i as usize % 10)
+        .for_each(|_| async {})
+        .await;
+}
+
+async fn batch_with_weight(stream: impl Stream<Item = i32>) {
+    stream
+        .min_batch_with_weight(1000, |i| *i as usize % 10)
+        .for_each(|_| async {})
+        .await;
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
     let rt = Runtime::new().unwrap();
 
@@ -22,6 +43,37 @@ fn criterion_benchmark(c: &mut Criterion) {
         );
     }
     group.finish();
+
+    let mut group = c.benchmark_group("pinned_vs_unpin");
+    for &size in &[1000, 100_000] {
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::new("pinned", size), &size, |bencher, &size| {
+            bencher
+                .to_async(&rt)
+                .iter(|| batch_pinned(stream::iter(0..size)));
+        });
+        group.bench_with_input(BenchmarkId::new("unpin", size), &size, |bencher, &size| {
+            bencher
+                .to_async(&rt)
+                .iter(|| batch_unpin(stream::iter(0..size)));
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("min_batch_with_weight");
+    for &size in &[10, 100, 1000, 10_000, 100_000] {
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &size,
+            |bencher, &size| {
+                bencher
+                    .to_async(&rt)
+                    .iter(|| batch_with_weight(stream::iter(0..size)));
+            },
+        );
+    }
+    group.finish();
 }
 
 criterion_group!(benches, criterion_benchmark);
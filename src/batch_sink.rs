@@ -0,0 +1,64 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+/// A container a batch is accumulated into, see [`MinBatch::new_in`](crate::min_batch::MinBatch::new_in).
+/// A batch is yielded by taking the whole container, leaving an empty one behind.
+pub trait BatchSink<T>: Default {
+    fn push(&mut self, item: T);
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Called before the first item of a batch is pushed, the default does nothing.
+    fn reserve(&mut self, _additional: usize) {}
+
+    fn take(&mut self) -> Self {
+        core::mem::take(self)
+    }
+}
+
+impl<T> BatchSink<T> for Vec<T> {
+    fn push(&mut self, item: T) {
+        Vec::push(self, item)
+    }
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        Vec::reserve(self, additional)
+    }
+}
+
+impl<T> BatchSink<T> for VecDeque<T> {
+    fn push(&mut self, item: T) {
+        VecDeque::push_back(self, item)
+    }
+
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        VecDeque::reserve(self, additional)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> BatchSink<A::Item> for smallvec::SmallVec<A> {
+    fn push(&mut self, item: A::Item) {
+        smallvec::SmallVec::push(self, item)
+    }
+
+    fn len(&self) -> usize {
+        smallvec::SmallVec::len(self)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        smallvec::SmallVec::reserve(self, additional)
+    }
+}
@@ -0,0 +1,248 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::{Fuse, Stream};
+
+use crate::batch_sink::BatchSink;
+use crate::weight::Weight;
+
+/// The batching state shared by the adapters, so that they all buffer, weigh and flush through
+/// the very same logic. It always tracks the weight of the batch it yields, most adapters simply
+/// discard it. Only [`MinBatchFold`](crate::min_batch_fold::MinBatchFold), which folds items into
+/// an accumulator, and [`Rebatch`](crate::rebatch::Rebatch), which cuts whole input batches, keep
+/// their own state.
+#[derive(Clone, Debug)]
+pub(crate) struct Batcher<F, C, W> {
+    pub(crate) current_batch_weight: W,
+    pub(crate) items: C,
+    /// Number of items in `items`, known without naming the item type of `C`.
+    pub(crate) buffered: usize,
+    pub(crate) min_batch_weight: W,
+    pub(crate) max_batch_len: Option<usize>,
+    pub(crate) max_batch_weight: Option<W>,
+    pub(crate) zero_progress_limit: Option<usize>,
+    zero_progress_len: usize,
+    pub(crate) drop_incomplete_tail: bool,
+    pub(crate) eager_flush: bool,
+    /// Capacity reserved before the first item of a batch is pushed.
+    pub(crate) reserve_len: usize,
+    /// Whether `reserve_len` follows the length of the last batch, as the next batch likely
+    /// holds a similar number of items.
+    reserve_last_len: bool,
+    pub(crate) count_fn: F,
+}
+
+impl<F, C, W> Batcher<F, C, W>
+where
+    C: Default,
+    W: Weight,
+{
+    pub(crate) fn new(min_batch_weight: W, count_fn: F) -> Self {
+        Batcher {
+            current_batch_weight: W::default(),
+            items: C::default(),
+            buffered: 0,
+            min_batch_weight,
+            max_batch_len: None,
+            max_batch_weight: None,
+            zero_progress_limit: None,
+            zero_progress_len: 0,
+            drop_incomplete_tail: false,
+            eager_flush: false,
+            reserve_len: 0,
+            reserve_last_len: false,
+            count_fn,
+        }
+    }
+
+    pub(crate) fn reserving(mut self, reserve_len: usize) -> Self {
+        self.reserve_len = reserve_len;
+        self
+    }
+
+    pub(crate) fn reserving_last_len(mut self) -> Self {
+        self.reserve_last_len = true;
+        self
+    }
+
+    /// Takes the batch in progress with its weight, leaving an empty one behind.
+    pub(crate) fn flush<T>(&mut self) -> (C, W)
+    where
+        C: BatchSink<T>,
+    {
+        if self.reserve_last_len {
+            self.reserve_len = self.items.len();
+        }
+        let batch_weight = core::mem::take(&mut self.current_batch_weight);
+        self.zero_progress_len = 0;
+        self.buffered = 0;
+        (self.items.take(), batch_weight)
+    }
+
+    pub(crate) fn take_pending<T>(&mut self) -> Option<(C, W)>
+    where
+        C: BatchSink<T>,
+    {
+        if self.items.is_empty() {
+            None
+        } else {
+            Some(self.flush())
+        }
+    }
+
+    pub(crate) fn reset<T>(&mut self)
+    where
+        C: BatchSink<T>,
+    {
+        drop(self.items.take());
+        self.buffered = 0;
+        self.current_batch_weight = W::default();
+        self.zero_progress_len = 0;
+    }
+
+    /// Whether the batch in progress is due under any of the configured thresholds.
+    pub(crate) fn is_full<T>(&self) -> bool
+    where
+        C: BatchSink<T>,
+    {
+        !self.items.is_empty()
+            && (self.current_batch_weight >= self.min_batch_weight
+                || self
+                    .max_batch_len
+                    .is_some_and(|max_len| self.items.len() >= max_len)
+                || self
+                    .max_batch_weight
+                    .is_some_and(|max_weight| self.current_batch_weight >= max_weight)
+                || self
+                    .zero_progress_limit
+                    .is_some_and(|limit| self.zero_progress_len >= limit))
+    }
+
+    /// Takes the batch in progress if it is due, e.g. after a lower `min_batch_weight` was set.
+    pub(crate) fn take_full<T>(&mut self) -> Option<(C, W)>
+    where
+        C: BatchSink<T>,
+    {
+        if !self.is_full() {
+            return None;
+        }
+        #[cfg(feature = "debug-invariants")]
+        debug_assert!(
+            self.current_batch_weight >= self.min_batch_weight
+                || self.max_batch_len.is_some()
+                || self.max_batch_weight.is_some()
+                || self.zero_progress_limit.is_some(),
+            "uncapped batch yielded under min_batch_weight"
+        );
+        Some(self.flush())
+    }
+
+    /// Adds `item` of `weight` to the batch in progress and returns the batch it completed. When
+    /// `item` would push a batch over `max_batch_weight`, that batch is returned instead and
+    /// `item` starts the next one, which [`take_full`](Self::take_full) yields if it is due.
+    pub(crate) fn push<T>(&mut self, item: T, weight: W) -> Option<(C, W)>
+    where
+        C: BatchSink<T>,
+    {
+        let full_batch = if !self.items.is_empty()
+            && self.max_batch_weight.is_some_and(|max_weight| {
+                self.current_batch_weight.saturating_add(weight) > max_weight
+            }) {
+            Some(self.flush())
+        } else {
+            None
+        };
+        self.add(item, weight);
+        full_batch.or_else(|| self.take_full())
+    }
+
+    /// Adds `item` of `weight` to the batch in progress without checking any threshold, for the
+    /// adapters that decide themselves when a batch is due.
+    pub(crate) fn add<T>(&mut self, item: T, weight: W)
+    where
+        C: BatchSink<T>,
+    {
+        if self.items.is_empty() {
+            self.items.reserve(self.reserve_len);
+        }
+        self.items.push(item);
+        self.buffered += 1;
+        self.current_batch_weight = self.current_batch_weight.saturating_add(weight);
+        if weight == W::default() {
+            self.zero_progress_len += 1;
+        } else {
+            self.zero_progress_len = 0;
+        }
+    }
+
+    /// Pushes an item that was yielded already, like the overlap a sliding window carries over.
+    /// It adds to the weight but not to `buffered`, as it is not lost when the adapter is
+    /// dropped, and it is never checked against the thresholds, only the next push is.
+    pub(crate) fn retain<T>(&mut self, item: T, weight: W)
+    where
+        C: BatchSink<T>,
+    {
+        self.items.push(item);
+        self.current_batch_weight = self.current_batch_weight.saturating_add(weight);
+    }
+
+    /// Takes the terminal batch once the inner stream has ended.
+    pub(crate) fn finish<T>(&mut self) -> Option<(C, W)>
+    where
+        C: BatchSink<T>,
+    {
+        if self.items.is_empty() {
+            None
+        } else if self.drop_incomplete_tail {
+            self.reset();
+            None
+        } else {
+            // only the terminal partial batch may be under weight without a cap
+            #[cfg(feature = "debug-invariants")]
+            debug_assert!(
+                self.current_batch_weight < self.min_batch_weight,
+                "terminal batch should have been yielded earlier"
+            );
+            Some(self.flush())
+        }
+    }
+
+    pub(crate) fn poll_next<S, T>(
+        &mut self,
+        mut stream: Pin<&mut Fuse<S>>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<(C, W)>>
+    where
+        S: Stream<Item = T>,
+        F: FnMut(&T) -> W,
+        C: BatchSink<T>,
+    {
+        loop {
+            if let Some(batch) = self.take_full() {
+                return Poll::Ready(Some(batch));
+            }
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let weight = (self.count_fn)(&item);
+                    if let Some(batch) = self.push(item, weight) {
+                        return Poll::Ready(Some(batch));
+                    }
+                }
+                Poll::Ready(None) => return Poll::Ready(self.finish()),
+                Poll::Pending if self.eager_flush && !self.items.is_empty() => {
+                    return Poll::Ready(Some(self.flush()));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Warns about the items a cancelled adapter loses, as they are dropped with it.
+#[cfg(feature = "drop-warning")]
+impl<F, C, W> Drop for Batcher<F, C, W> {
+    fn drop(&mut self) {
+        if self.buffered > 0 {
+            crate::drop_warning::warn(self.buffered);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use alloc::vec::Vec;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+use std::time::Duration;
+use tokio::time::{sleep, Instant, Sleep};
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+/// Composes several batching policies into a single adapter, see [`MinBatchBuilder::build`].
+///
+/// A batch is yielded as soon as any of the configured policies triggers:
+///  - `min_weight`: the batch weighs at least `n`,
+///  - `max_weight`: the incoming item would make the batch weigh more than `n`, in which case
+///    the batch is yielded without it and the item starts the next one, or the batch weighs
+///    exactly `n`,
+///  - `max_len`: the batch holds `n` items,
+///  - `idle_timeout`: no item arrived within `d` since the last one,
+///  - `deadline`: `d` elapsed since the first item of the batch arrived.
+///
+/// As every policy only ever cuts a batch, conflicting ones resolve to the tighter limit, e.g.
+/// a `max_weight` lower than `min_weight` yields batches of at most `max_weight` that never
+/// reach `min_weight`. Items are never split, a single item meeting or exceeding a weight limit
+/// is yielded alone. Without any policy the whole stream is yielded as a single batch, and the
+/// last partial batch is always yielded when the stream ends.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MinBatchBuilder {
+    min_weight: Option<usize>,
+    max_weight: Option<usize>,
+    max_len: Option<usize>,
+    idle_timeout: Option<Duration>,
+    deadline: Option<Duration>,
+}
+
+impl MinBatchBuilder {
+    pub fn new() -> Self {
+        MinBatchBuilder::default()
+    }
+
+    pub fn min_weight(mut self, min_weight: usize) -> Self {
+        self.min_weight = Some(min_weight);
+        self
+    }
+
+    pub fn max_weight(mut self, max_weight: usize) -> Self {
+        self.max_weight = Some(max_weight);
+        self
+    }
+
+    /// A `max_len` of `0` behaves like `1`.
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len.max(1));
+        self
+    }
+
+    pub fn idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    pub fn build<S, F, T>(self, stream: S, count_fn: F) -> MinBatchConfigured<S, F, T>
+    where
+        S: Stream<Item = T>,
+        F: FnMut(&T) -> usize,
+    {
+        // without `min_weight` only the other policies cut a batch
+        let mut core = Batcher::new(self.min_weight.unwrap_or(usize::MAX), count_fn)
+            .reserving(self.capacity());
+        core.max_batch_weight = self.max_weight;
+        core.max_batch_len = self.max_len;
+        MinBatchConfigured {
+            stream: stream.fuse(),
+            idle: None,
+            deadline: None,
+            core,
+            policies: self,
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        match (self.min_weight, self.max_len) {
+            (Some(min_weight), Some(max_len)) => initial_capacity(min_weight).min(max_len),
+            (Some(min_weight), None) => initial_capacity(min_weight),
+            (None, Some(max_len)) => initial_capacity(max_len),
+            (None, None) => 0,
+        }
+    }
+}
+
+pin_project! {
+    /// The adapter configured by [`MinBatchBuilder`], evaluating all of its policies on every
+    /// poll.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchConfigured<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        #[pin]
+        idle: Option<Sleep>,
+        #[pin]
+        deadline: Option<Sleep>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        policies: MinBatchBuilder,
+    }
+}
+
+impl<S, F, T> Stream for MinBatchConfigured<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        let policies = *me.policies;
+        loop {
+            // an item moved into the next batch may fill it on its own
+            if let Some((items, _)) = me.core.take_full() {
+                me.idle.set(None);
+                me.deadline.set(None);
+                return Poll::Ready(Some(items));
+            }
+            let item = match me.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => item,
+                Poll::Ready(None) => {
+                    me.idle.set(None);
+                    me.deadline.set(None);
+                    return Poll::Ready(me.core.finish().map(|(items, _)| items));
+                }
+                Poll::Pending => {
+                    let idle = me
+                        .idle
+                        .as_mut()
+                        .as_pin_mut()
+                        .is_some_and(|idle| idle.poll(cx).is_ready());
+                    let deadline = me
+                        .deadline
+                        .as_mut()
+                        .as_pin_mut()
+                        .is_some_and(|deadline| deadline.poll(cx).is_ready());
+                    if idle || deadline {
+                        if let Some((items, _)) = me.core.take_pending() {
+                            me.idle.set(None);
+                            me.deadline.set(None);
+                            return Poll::Ready(Some(items));
+                        }
+                    }
+                    return Poll::Pending;
+                }
+            };
+            let weight = (me.core.count_fn)(&item);
+            if me.core.items.is_empty() {
+                restart(me.deadline.as_mut(), policies.deadline);
+            }
+            if let Some((items, _)) = me.core.push(item, weight) {
+                me.idle.set(None);
+                me.deadline.set(None);
+                if !me.core.items.is_empty() {
+                    // the item that would have exceeded `max_weight` starts the next batch
+                    restart(me.deadline.as_mut(), policies.deadline);
+                    restart(me.idle.as_mut(), policies.idle_timeout);
+                }
+                return Poll::Ready(Some(items));
+            }
+            // a stream that is always ready never reaches the timers, so check the deadline here
+            let expired = me
+                .deadline
+                .as_ref()
+                .as_pin_ref()
+                .is_some_and(|deadline| deadline.deadline() <= Instant::now());
+            if expired {
+                me.idle.set(None);
+                me.deadline.set(None);
+                return Poll::Ready(Some(me.core.flush().0));
+            }
+            restart(me.idle.as_mut(), policies.idle_timeout);
+        }
+    }
+}
+
+/// Starts `timer` over for `duration`, leaving it untouched when the policy is not configured.
+fn restart(mut timer: Pin<&mut Option<Sleep>>, duration: Option<Duration>) {
+    let Some(duration) = duration else {
+        return;
+    };
+    match timer.as_mut().as_pin_mut() {
+        Some(running) => running.reset(Instant::now() + duration),
+        None => timer.set(Some(sleep(duration))),
+    }
+}
@@ -0,0 +1,17 @@
+use alloc::vec::Vec;
+use futures::stream::Stream;
+use futures::StreamExt;
+
+/// Drains every batch of `batches` into `outer`, which is cleared first. Unlike `collect`, the
+/// caller owns the outer `Vec`, so its capacity is reused when draining repeatedly, e.g. across
+/// benchmark iterations.
+pub async fn collect_batches_into<S>(batches: S, outer: &mut Vec<S::Item>)
+where
+    S: Stream,
+{
+    outer.clear();
+    let mut batches = core::pin::pin!(batches);
+    while let Some(batch) = batches.next().await {
+        outer.push(batch);
+    }
+}
@@ -0,0 +1,82 @@
+use alloc::sync::Arc;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::channel::oneshot;
+use futures::stream::{Fuse, FuturesUnordered, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+use rayon::ThreadPool;
+
+pin_project! {
+    /// Sends every batch of the inner stream to a rayon `pool`, where it is processed by `f` off
+    /// the async runtime, and yields the results as they complete, hence not necessarily in the
+    /// order of the batches.
+    ///
+    /// At most `pool.current_num_threads()` batches are in flight at once, the inner stream is
+    /// not polled while the pool is saturated. The adapter keeps the pool alive until it is
+    /// dropped. A panic of `f` is handled by the pool's panic handler, which aborts by default,
+    /// if the pool survives it, the adapter panics as the result is lost.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct DispatchRayon<S, G, R> where
+    S: Stream,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pool: Arc<ThreadPool>,
+        f: Arc<G>,
+        pub(crate) in_flight: FuturesUnordered<oneshot::Receiver<R>>,
+    }
+}
+
+impl<S, G, R> DispatchRayon<S, G, R>
+where
+    S: Stream,
+    S::Item: Send + 'static,
+    G: Fn(S::Item) -> R + Send + Sync + 'static,
+    R: Send + 'static,
+{
+    pub fn new(stream: S, pool: Arc<ThreadPool>, f: G) -> Self {
+        DispatchRayon {
+            stream: stream.fuse(),
+            pool,
+            f: Arc::new(f),
+            in_flight: FuturesUnordered::new(),
+        }
+    }
+}
+
+impl<S, G, R> Stream for DispatchRayon<S, G, R>
+where
+    S: Stream,
+    S::Item: Send + 'static,
+    G: Fn(S::Item) -> R + Send + Sync + 'static,
+    R: Send + 'static,
+{
+    type Item = R;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        let max_in_flight = me.pool.current_num_threads().max(1);
+        while me.in_flight.len() < max_in_flight {
+            match me.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(batch)) => {
+                    let (tx, rx) = oneshot::channel();
+                    let f = Arc::clone(me.f);
+                    me.pool.spawn(move || {
+                        let _ = tx.send(f(batch));
+                    });
+                    me.in_flight.push(rx);
+                }
+                Poll::Ready(None) | Poll::Pending => break,
+            }
+        }
+        match me.in_flight.poll_next_unpin(cx) {
+            Poll::Ready(Some(Ok(result))) => Poll::Ready(Some(result)),
+            Poll::Ready(Some(Err(oneshot::Canceled))) => {
+                panic!("a batch dispatched to the rayon pool panicked")
+            }
+            Poll::Ready(None) if me.stream.is_done() => Poll::Ready(None),
+            Poll::Ready(None) | Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use alloc::boxed::Box;
+use core::fmt;
+use std::sync::{PoisonError, RwLock};
+
+/// What an adapter lost when it was dropped, passed to the hook installed by [`set_drop_hook`].
+/// Its `Display` is the default warning, so a hook can log it as is or pick its fields, e.g.
+/// `set_drop_hook(|warning| tracing::warn!(buffered = warning.buffered, "{warning}"))`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DropWarning {
+    /// Number of buffered items dropped with the adapter.
+    pub buffered: usize,
+}
+
+impl fmt::Display for DropWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min-batch: adapter dropped with {} buffered items",
+            self.buffered
+        )
+    }
+}
+
+/// Receives every [`DropWarning`], see [`set_drop_hook`].
+pub type DropHook = Box<dyn Fn(&DropWarning) + Send + Sync>;
+
+// `None` stands for the default hook printing to stderr
+static HOOK: RwLock<Option<DropHook>> = RwLock::new(None);
+
+/// Replaces the process-wide hook called when an adapter is dropped with items still buffered,
+/// e.g. to forward the warning to `tracing` or to the logger of the application. The default
+/// hook prints it to stderr.
+pub fn set_drop_hook<H>(hook: H)
+where
+    H: Fn(&DropWarning) + Send + Sync + 'static,
+{
+    *HOOK.write().unwrap_or_else(PoisonError::into_inner) = Some(Box::new(hook));
+}
+
+pub(crate) fn warn(buffered: usize) {
+    let warning = DropWarning { buffered };
+    match &*HOOK.read().unwrap_or_else(PoisonError::into_inner) {
+        Some(hook) => hook(&warning),
+        None => std::eprintln!("{warning}"),
+    }
+}
@@ -1,48 +1,1799 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::future::Future;
+#[cfg(feature = "std")]
+use core::hash::Hash;
+use core::num::NonZeroUsize;
+use core::ops::Deref;
+use core::sync::atomic::AtomicUsize;
+#[cfg(feature = "alloc")]
+use futures::stream::BoxStream;
 use futures::stream::{FusedStream, Stream};
+#[cfg(feature = "std")]
+use std::panic::UnwindSafe;
+#[cfg(feature = "std")]
+use std::time::Duration;
+#[cfg(feature = "std")]
+use tokio::sync::mpsc::{error::SendError, Sender, UnboundedSender};
+#[cfg(feature = "std")]
+use tokio_util::sync::CancellationToken;
 
-use crate::{min_batch::MinBatch, min_batch_with_weight::MinBatchWithWeight};
+#[cfg(feature = "rayon")]
+use crate::dispatch_rayon::DispatchRayon;
+#[cfg(feature = "async-closure")]
+use crate::min_batch_async;
+#[cfg(feature = "alloc")]
+use crate::min_batch_boxed::MinBatchBoxed;
+#[cfg(feature = "alloc")]
+use crate::min_batch_ordered_parallel::MinBatchOrderedParallel;
+use crate::{
+    batch_sink::BatchSink,
+    collect_batches_into,
+    mem_size::MemSize,
+    min_batch::{MinBatch, WeightFn},
+    min_batch_2d::MinBatch2d,
+    min_batch_absorb_tail::MinBatchAbsorbTail,
+    min_batch_adaptive::MinBatchAdaptive,
+    min_batch_async_weight::MinBatchAsyncWeight,
+    min_batch_by_key::MinBatchByKey,
+    min_batch_collect::MinBatchCollect,
+    min_batch_credited::{Credits, MinBatchCredited},
+    min_batch_decayed::MinBatchDecayed,
+    min_batch_enumerate::MinBatchEnumerate,
+    min_batch_exclusive::MinBatchExclusive,
+    min_batch_filter::MinBatchFilter,
+    min_batch_fold::MinBatchFold,
+    min_batch_indexed::MinBatchIndexed,
+    min_batch_inspect::MinBatchInspect,
+    min_batch_lookahead::MinBatchLookahead,
+    min_batch_nonzero::MinBatchNonZero,
+    min_batch_observed::MinBatchObserved,
+    min_batch_on_sentinel::MinBatchOnSentinel,
+    min_batch_prekeyed::MinBatchPrekeyed,
+    min_batch_ranged::MinBatchRanged,
+    min_batch_ref::MinBatchRef,
+    min_batch_results::{ErrorPolicy, MinBatchResults},
+    min_batch_shared::MinBatchShared,
+    min_batch_sliding::MinBatchSliding,
+    min_batch_sorted_by_key::MinBatchSortedByKey,
+    min_batch_split::MinBatchSplit,
+    min_batch_tagged::MinBatchTagged,
+    min_batch_target::MinBatchTarget,
+    min_batch_then_map::MinBatchThenMap,
+    min_batch_try::MinBatchTry,
+    min_batch_unpin::MinBatchUnpin,
+    min_batch_until::MinBatchUntil,
+    min_batch_warmup::MinBatchWarmup,
+    min_batch_when::MinBatchWhen,
+    min_batch_with_barriers::MinBatchWithBarriers,
+    min_batch_with_count::MinBatchWithCount,
+    min_batch_with_drop_handler::MinBatchWithDropHandler,
+    min_batch_with_histogram::{Histogram, MinBatchWithHistogram},
+    min_batch_with_stats::MinBatchWithStats,
+    min_batch_with_weight::MinBatchWithWeight,
+    min_batch_zip::MinBatchZip,
+    rebatch::Rebatch,
+    take_batches::TakeBatches,
+    weight::Weight,
+};
+#[cfg(feature = "std")]
+use crate::{
+    builder::MinBatchConfigured,
+    for_each_batch_parallel,
+    min_batch_cancellable::MinBatchCancellable,
+    min_batch_catching::MinBatchCatching,
+    min_batch_deadline::MinBatchDeadline,
+    min_batch_deadline_tagged::MinBatchDeadlineTagged,
+    min_batch_dedup::MinBatchDedup,
+    min_batch_eager_pull::MinBatchEagerPull,
+    min_batch_for_cpus::{available_cpus, weight_per_cpu},
+    min_batch_grouped::MinBatchGrouped,
+    min_batch_pooled::{BatchPool, MinBatchPooled},
+    min_batch_rate_limited::MinBatchRateLimited,
+    min_batch_reporting::MinBatchReporting,
+    min_batch_timestamped::MinBatchTimestamped,
+    min_batch_to_sender,
+    min_batch_with_heartbeat::MinBatchWithHeartbeat,
+    min_batch_with_timeout::MinBatchWithTimeout,
+};
 
 pub trait MinBatchExt: Stream {
+    /// Processes the batches of this stream by `f` on a rayon `pool`, see [`DispatchRayon`].
+    #[cfg(feature = "rayon")]
+    fn dispatch_rayon<G, R>(self, pool: Arc<rayon::ThreadPool>, f: G) -> DispatchRayon<Self, G, R>
+    where
+        Self: Sized,
+        Self::Item: Send + 'static,
+        G: Fn(Self::Item) -> R + Send + Sync + 'static,
+        R: Send + 'static,
+    {
+        DispatchRayon::new(self, pool, f)
+    }
+
+    /// Drains the batches of this stream into `outer`, see
+    /// [`collect_batches_into`](crate::collect_batches_into::collect_batches_into).
+    fn collect_batches_into(self, outer: &mut Vec<Self::Item>) -> impl Future<Output = ()>
+    where
+        Self: Sized,
+    {
+        collect_batches_into::collect_batches_into(self, outer)
+    }
+
+    /// Processes the batches of this stream with up to `concurrency` spawned futures at once, see
+    /// [`for_each_batch_parallel`](crate::for_each_batch_parallel::for_each_batch_parallel).
+    #[cfg(feature = "std")]
+    fn for_each_batch_parallel<G, Fut>(self, concurrency: usize, f: G) -> impl Future<Output = ()>
+    where
+        Self: Sized,
+        G: Fn(Self::Item) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        for_each_batch_parallel::for_each_batch_parallel(self, concurrency, f)
+    }
+
     fn min_batch<F>(self, min_batch_weight: usize, count_fn: F) -> MinBatch<Self, F, Self::Item>
     where
         Self: Sized,
-        F: Fn(&Self::Item) -> usize,
+        F: FnMut(&Self::Item) -> usize,
     {
         MinBatch::new(self, min_batch_weight, count_fn)
     }
 
-    fn min_batch_with_weight<F>(
+    /// Batches items until a batch occupies at least `min_batch_bytes` bytes inline, the weight of
+    /// an item is `size_of::<Self::Item>()`, so every batch of a fixed-size type holds the same
+    /// number of items. Heap memory owned by items is not counted, see
+    /// [`min_batch_by_mem_size`](Self::min_batch_by_mem_size). Zero-sized items weigh `0`.
+    fn min_batch_by_mem(
+        self,
+        min_batch_bytes: usize,
+    ) -> MinBatch<Self, WeightFn<Self::Item>, Self::Item>
+    where
+        Self: Sized,
+    {
+        MinBatch::new(self, min_batch_bytes, inline_size::<Self::Item>)
+    }
+
+    /// Like [`min_batch_by_mem`](Self::min_batch_by_mem) but items are weighed by
+    /// [`MemSize::mem_size`], which accounts for the heap memory they own as well.
+    fn min_batch_by_mem_size(
+        self,
+        min_batch_bytes: usize,
+    ) -> MinBatch<Self, WeightFn<Self::Item>, Self::Item>
+    where
+        Self: Sized,
+        Self::Item: MemSize,
+    {
+        MinBatch::new(self, min_batch_bytes, Self::Item::mem_size)
+    }
+
+    #[cfg(feature = "std")]
+    fn min_batch_cancellable<F>(
         self,
         min_batch_weight: usize,
         count_fn: F,
-    ) -> MinBatchWithWeight<Self, F, Self::Item>
+        token: CancellationToken,
+    ) -> MinBatchCancellable<Self, F, Self::Item>
     where
         Self: Sized,
-        F: Fn(&Self::Item) -> usize,
+        F: FnMut(&Self::Item) -> usize,
     {
-        MinBatchWithWeight::new(self, min_batch_weight, count_fn)
+        MinBatchCancellable::new(self, min_batch_weight, count_fn, token)
     }
-}
 
-// Implement the trait for all types that implement Stream
-impl<T: ?Sized> MinBatchExt for T where T: Stream {}
+    fn min_batch_capped<F>(
+        self,
+        min_batch_weight: usize,
+        max_batch_len: usize,
+        count_fn: F,
+    ) -> MinBatch<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatch::new_capped(self, min_batch_weight, max_batch_len, count_fn)
+    }
+
+    fn min_batch_2d<F>(
+        self,
+        min_batch_weight: usize,
+        min_batch_len: usize,
+        count_fn: F,
+    ) -> MinBatch2d<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatch2d::new(self, min_batch_weight, min_batch_len, count_fn)
+    }
+
+    fn min_batch_absorb_tail<F>(
+        self,
+        min_batch_weight: usize,
+        min_tail_weight: usize,
+        count_fn: F,
+    ) -> MinBatchAbsorbTail<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchAbsorbTail::new(self, min_batch_weight, min_tail_weight, count_fn)
+    }
+
+    fn min_batch_adaptive<F, A>(
+        self,
+        base_weight: usize,
+        count_fn: F,
+        backlog: Arc<AtomicUsize>,
+        adjust_fn: A,
+    ) -> MinBatchAdaptive<Self, F, A, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+        A: Fn(usize, usize) -> usize,
+    {
+        MinBatchAdaptive::new(self, base_weight, count_fn, backlog, adjust_fn)
+    }
+
+    /// Batches items weighed by an async closure, see
+    /// [`min_batch_async`](crate::min_batch_async::min_batch_async).
+    #[cfg(feature = "async-closure")]
+    fn min_batch_async<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> impl Stream<Item = Vec<Self::Item>>
+    where
+        Self: Sized,
+        F: AsyncFnMut(&Self::Item) -> usize,
+    {
+        min_batch_async::min_batch_async(self, min_batch_weight, count_fn)
+    }
+
+    fn min_batch_async_weight<F, Fut>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchAsyncWeight<Self, F, Fut, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> Fut,
+        Fut: Future<Output = usize>,
+    {
+        MinBatchAsyncWeight::new(self, min_batch_weight, count_fn)
+    }
+
+    /// Batches items exposing their bytes until a batch holds at least `min_batch_bytes` bytes,
+    /// the weight of an item is `item.as_ref().len()`.
+    fn min_batch_bytes(
+        self,
+        min_batch_bytes: usize,
+    ) -> MinBatch<Self, WeightFn<Self::Item>, Self::Item>
+    where
+        Self: Sized,
+        Self::Item: AsRef<[u8]>,
+    {
+        MinBatch::new(self, min_batch_bytes, byte_len::<Self::Item>)
+    }
+
+    /// Batches like [`min_batch`](Self::min_batch) and erases the type of the adapter into a
+    /// [`BoxStream`], for signatures at API boundaries that would otherwise spell out the whole
+    /// `MinBatch<..>`, at the cost of a dynamic dispatch per poll. A `BoxStream` is `Send` and the
+    /// `'static` one owns everything it polls, hence the stream, its items and `count_fn` must
+    /// all be `Send + 'static`.
+    #[cfg(feature = "alloc")]
+    fn min_batch_box_stream<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> BoxStream<'static, Vec<Self::Item>>
+    where
+        Self: Sized + Send + 'static,
+        Self::Item: Send + 'static,
+        F: FnMut(&Self::Item) -> usize + Send + 'static,
+    {
+        Box::pin(MinBatch::new(self, min_batch_weight, count_fn))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn min_batch_boxed<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchBoxed<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchBoxed::new(self, min_batch_weight, count_fn)
+    }
+
+    fn min_batch_by_key<KF, F, K>(
+        self,
+        min_batch_weight: usize,
+        key_fn: KF,
+        count_fn: F,
+    ) -> MinBatchByKey<Self, KF, F, Self::Item, K>
+    where
+        Self: Sized,
+        KF: Fn(&Self::Item) -> K,
+        K: PartialEq,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchByKey::new(self, min_batch_weight, key_fn, count_fn)
+    }
+
+    #[cfg(feature = "std")]
+    fn min_batch_dedup<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchDedup<Self, F, Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Hash + Eq + Clone,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchDedup::new(self, min_batch_weight, count_fn)
+    }
+
+    #[cfg(feature = "std")]
+    fn min_batch_catching<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchCatching<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize + UnwindSafe,
+    {
+        MinBatchCatching::new(self, min_batch_weight, count_fn)
+    }
+
+    /// Batches into any `C: Default + Extend<Self::Item>`, see [`MinBatchCollect`]. `C` usually
+    /// has to be named, as in `min_batch_collect::<String, _>(..)`.
+    fn min_batch_collect<C, F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchCollect<Self, F, Self::Item, C>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+        C: Default + Extend<Self::Item>,
+    {
+        MinBatchCollect::new(self, min_batch_weight, count_fn)
+    }
+
+    fn min_batch_credited<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        credits: Arc<Credits>,
+    ) -> MinBatchCredited<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchCredited::new(self, min_batch_weight, count_fn, credits)
+    }
+
+    fn min_batch_decayed<F>(
+        self,
+        threshold: f64,
+        decay: f64,
+        count_fn: F,
+    ) -> MinBatchDecayed<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchDecayed::new(self, threshold, decay, count_fn)
+    }
+
+    fn min_batch_decayed_capped<F>(
+        self,
+        threshold: f64,
+        decay: f64,
+        max_batch_len: usize,
+        count_fn: F,
+    ) -> MinBatchDecayed<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchDecayed::new_capped(self, threshold, decay, max_batch_len, count_fn)
+    }
+
+    #[cfg(feature = "std")]
+    fn min_batch_deadline<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        max_latency: Duration,
+    ) -> MinBatchDeadline<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchDeadline::new(self, min_batch_weight, count_fn, max_latency)
+    }
+
+    #[cfg(feature = "std")]
+    fn min_batch_deadline_tagged<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        max_assembly: Duration,
+    ) -> MinBatchDeadlineTagged<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchDeadlineTagged::new(self, min_batch_weight, count_fn, max_assembly)
+    }
+
+    fn min_batch_deque<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatch<Self, F, Self::Item, VecDeque<Self::Item>>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatch::new_in(self, min_batch_weight, count_fn)
+    }
+
+    #[cfg(feature = "std")]
+    fn min_batch_eager_pull<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        capacity: usize,
+    ) -> MinBatchEagerPull<Self::Item, F>
+    where
+        Self: Sized + Send + 'static,
+        Self::Item: Send + 'static,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchEagerPull::new(self, min_batch_weight, count_fn, capacity)
+    }
+
+    fn min_batch_enumerate<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchEnumerate<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchEnumerate::new(self, min_batch_weight, count_fn)
+    }
+
+    fn min_batch_exclusive<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchExclusive<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchExclusive::new(self, min_batch_weight, count_fn)
+    }
+
+    fn min_batch_filter<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchFilter<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> Option<usize>,
+    {
+        MinBatchFilter::new(self, min_batch_weight, count_fn)
+    }
+
+    fn min_batch_fold<F, A, G>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        init: A,
+        fold_fn: G,
+    ) -> MinBatchFold<Self, F, Self::Item, A, G>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+        A: Clone,
+        G: Fn(A, Self::Item) -> A,
+    {
+        MinBatchFold::new(self, min_batch_weight, count_fn, init, fold_fn)
+    }
+
+    fn min_batch_in<C, F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatch<Self, F, Self::Item, C>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+        C: BatchSink<Self::Item>,
+    {
+        MinBatch::new_in(self, min_batch_weight, count_fn)
+    }
+
+    /// Batches like [`min_batch`](Self::min_batch) with a `min_batch_weight` of
+    /// `total_weight_hint / available_cpus()`, so that a stream of about `total_weight_hint`
+    /// gives each CPU roughly one batch, see [`weight_per_cpu`]. When the available parallelism
+    /// can't be determined, a single CPU is assumed and the whole hint makes one batch.
+    #[cfg(feature = "std")]
+    fn min_batch_for_cpus<F>(
+        self,
+        total_weight_hint: usize,
+        count_fn: F,
+    ) -> MinBatch<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        let min_batch_weight = weight_per_cpu(total_weight_hint, available_cpus());
+        MinBatch::new(self, min_batch_weight, count_fn)
+    }
+
+    #[cfg(feature = "std")]
+    fn min_batch_grouped<F, K, V>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchGrouped<Self, F, K, V>
+    where
+        Self: Sized + Stream<Item = (K, V)>,
+        F: FnMut(&(K, V)) -> usize,
+        K: Hash + Eq,
+    {
+        MinBatchGrouped::new(self, min_batch_weight, count_fn)
+    }
+
+    fn min_batch_indexed<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchIndexed<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item, usize) -> usize,
+    {
+        MinBatchIndexed::new(self, min_batch_weight, count_fn)
+    }
+
+    fn min_batch_inspect<F, O>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        inspect: O,
+    ) -> MinBatchInspect<Self, F, O, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+        O: Fn(&[Self::Item], usize),
+    {
+        MinBatchInspect::new(self, min_batch_weight, count_fn, inspect)
+    }
+
+    fn min_batch_lookahead<F, P>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        fit_fn: P,
+    ) -> MinBatchLookahead<Self, F, P, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+        P: Fn(usize, usize) -> bool,
+    {
+        MinBatchLookahead::new(self, min_batch_weight, count_fn, fit_fn)
+    }
+
+    fn min_batch_nonzero<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchNonZero<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> NonZeroUsize,
+    {
+        MinBatchNonZero::new(self, min_batch_weight, count_fn)
+    }
+
+    fn min_batch_observed<F, O>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        on_flush: O,
+    ) -> MinBatchObserved<Self, F, O, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+        O: Fn(usize, usize),
+    {
+        MinBatchObserved::new(self, min_batch_weight, count_fn, on_flush)
+    }
+
+    fn min_batch_on_sentinel<F, P>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        is_sentinel: P,
+    ) -> MinBatchOnSentinel<Self, F, P, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+        P: Fn(&Self::Item) -> bool,
+    {
+        MinBatchOnSentinel::new(self, min_batch_weight, count_fn, is_sentinel)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn min_batch_ordered_parallel<F, G, Fut>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        concurrency: usize,
+        f: G,
+    ) -> MinBatchOrderedParallel<Self, F, G, Fut, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+        G: Fn(Vec<Self::Item>) -> Fut,
+        Fut: Future,
+    {
+        MinBatchOrderedParallel::new(self, min_batch_weight, count_fn, concurrency, f)
+    }
+
+    #[cfg(feature = "std")]
+    fn min_batch_pooled<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        pool: BatchPool<Self::Item>,
+    ) -> MinBatchPooled<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchPooled::new(self, min_batch_weight, count_fn, pool)
+    }
+
+    fn min_batch_prekeyed<T>(self, min_batch_weight: usize) -> MinBatchPrekeyed<Self, T>
+    where
+        Self: Sized + Stream<Item = (T, usize)>,
+    {
+        MinBatchPrekeyed::new(self, min_batch_weight)
+    }
+
+    /// Like [`min_batch_prekeyed`](Self::min_batch_prekeyed) but the batches keep the
+    /// `(item, weight)` pairs.
+    fn min_batch_prekeyed_tagged<T>(
+        self,
+        min_batch_weight: usize,
+    ) -> MinBatch<Self, WeightFn<(T, usize)>, (T, usize)>
+    where
+        Self: Sized + Stream<Item = (T, usize)>,
+    {
+        MinBatch::new(self, min_batch_weight, prekeyed_weight::<T>)
+    }
+
+    fn min_batch_ranged<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchRanged<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchRanged::new(self, min_batch_weight, count_fn)
+    }
+
+    #[cfg(feature = "std")]
+    fn min_batch_rate_limited<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        min_interval: Duration,
+    ) -> MinBatchRateLimited<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchRateLimited::new(self, min_batch_weight, count_fn, min_interval)
+    }
+
+    fn min_batch_ref<F, T>(self, min_batch_weight: usize, count_fn: F) -> MinBatchRef<Self, F, T>
+    where
+        Self: Sized,
+        Self::Item: Deref<Target = T>,
+        T: ?Sized,
+        F: FnMut(&T) -> usize,
+    {
+        MinBatchRef::new(self, min_batch_weight, count_fn)
+    }
+
+    #[cfg(feature = "std")]
+    fn min_batch_reporting<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        weights: UnboundedSender<usize>,
+    ) -> MinBatchReporting<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchReporting::new(self, min_batch_weight, count_fn, weights)
+    }
+
+    fn min_batch_results<F, T, E>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchResults<Self, F, T, E>
+    where
+        Self: Sized + Stream<Item = Result<T, E>>,
+        F: FnMut(&T) -> usize,
+    {
+        MinBatchResults::new(self, min_batch_weight, count_fn)
+    }
+
+    fn min_batch_shared<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchShared<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchShared::new(self, min_batch_weight, count_fn)
+    }
+
+    fn min_batch_sliding<F>(
+        self,
+        min_batch_weight: usize,
+        overlap_weight: usize,
+        count_fn: F,
+    ) -> MinBatchSliding<Self, F, Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchSliding::new(self, min_batch_weight, overlap_weight, count_fn)
+    }
+
+    fn min_batch_sorted_by_key<F, KF, K>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        key_fn: KF,
+    ) -> MinBatchSortedByKey<Self, F, KF, Self::Item, K>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+        KF: Fn(&Self::Item) -> K,
+        K: Ord,
+    {
+        MinBatchSortedByKey::new(self, min_batch_weight, count_fn, key_fn)
+    }
+
+    fn min_batch_split<F, P>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        split_fn: P,
+    ) -> MinBatchSplit<Self, F, P, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+        P: Fn(Self::Item) -> Vec<Self::Item>,
+    {
+        MinBatchSplit::new(self, min_batch_weight, count_fn, split_fn)
+    }
+
+    fn min_batch_tagged<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchTagged<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchTagged::new(self, min_batch_weight, count_fn)
+    }
+
+    fn min_batch_target<F>(
+        self,
+        target: usize,
+        tolerance: usize,
+        count_fn: F,
+    ) -> MinBatchTarget<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchTarget::new(self, target, tolerance, count_fn)
+    }
+
+    fn min_batch_then_map<F, G, U>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        map_fn: G,
+    ) -> MinBatchThenMap<Self, F, G, Self::Item, U>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+        G: Fn(Vec<Self::Item>) -> U,
+    {
+        MinBatchThenMap::new(self, min_batch_weight, count_fn, map_fn)
+    }
+
+    #[cfg(feature = "std")]
+    fn min_batch_timestamped<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchTimestamped<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchTimestamped::new(self, min_batch_weight, count_fn)
+    }
+
+    /// Sends every batch of at least `min_batch_weight` to `sender`, see
+    /// [`min_batch_to_sender`](crate::min_batch_to_sender::min_batch_to_sender).
+    #[cfg(feature = "std")]
+    fn min_batch_to_sender<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        sender: Sender<Vec<Self::Item>>,
+    ) -> impl Future<Output = Result<(), SendError<Vec<Self::Item>>>>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        min_batch_to_sender::min_batch_to_sender(self, min_batch_weight, count_fn, sender)
+    }
+
+    fn min_batch_try<F, E>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchTry<Self, F, Self::Item, E>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> Result<usize, E>,
+    {
+        MinBatchTry::new(self, min_batch_weight, count_fn)
+    }
+
+    fn min_batch_unpin<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchUnpin<Self, F, Self::Item>
+    where
+        Self: Sized + Unpin,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchUnpin::new(self, min_batch_weight, count_fn)
+    }
+
+    fn min_batch_until<F, P>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        flush_if: P,
+    ) -> MinBatchUntil<Self, F, P, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+        P: Fn(&[Self::Item]) -> bool,
+    {
+        MinBatchUntil::new(self, min_batch_weight, count_fn, flush_if)
+    }
+
+    fn min_batch_warmup<F>(
+        self,
+        first_batch_weight: usize,
+        steady_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchWarmup<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchWarmup::new(self, first_batch_weight, steady_batch_weight, count_fn)
+    }
+
+    fn min_batch_when<F, P>(
+        self,
+        count_fn: F,
+        should_flush: P,
+    ) -> MinBatchWhen<Self, F, P, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+        P: Fn(&[Self::Item], usize) -> bool,
+    {
+        MinBatchWhen::new(self, count_fn, should_flush)
+    }
+
+    fn min_batch_with_barriers<F, P>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        barrier_fn: P,
+    ) -> MinBatchWithBarriers<Self, F, P, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+        P: Fn(&Self::Item) -> bool,
+    {
+        MinBatchWithBarriers::new(self, min_batch_weight, count_fn, barrier_fn)
+    }
+
+    fn min_batch_with_count<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchWithCount<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchWithCount::new(self, min_batch_weight, count_fn)
+    }
+
+    fn min_batch_with_drop_handler<F, D>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        on_drop: D,
+    ) -> MinBatchWithDropHandler<Self, F, D, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+        D: FnOnce(Vec<Self::Item>),
+    {
+        MinBatchWithDropHandler::new(self, min_batch_weight, count_fn, on_drop)
+    }
+
+    #[cfg(feature = "std")]
+    fn min_batch_with_heartbeat<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        interval: Duration,
+    ) -> MinBatchWithHeartbeat<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchWithHeartbeat::new(self, min_batch_weight, count_fn, interval)
+    }
+
+    fn min_batch_with_histogram<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        histogram: Histogram,
+    ) -> MinBatchWithHistogram<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchWithHistogram::new(self, min_batch_weight, count_fn, histogram)
+    }
+
+    fn min_batch_with_stats<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+    ) -> MinBatchWithStats<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchWithStats::new(self, min_batch_weight, count_fn)
+    }
+
+    #[cfg(feature = "std")]
+    fn min_batch_with_timeout<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        timeout: Duration,
+    ) -> MinBatchWithTimeout<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchWithTimeout::new(self, min_batch_weight, count_fn, timeout)
+    }
+
+    fn min_batch_with_weight<F, W>(
+        self,
+        min_batch_weight: W,
+        count_fn: F,
+    ) -> MinBatchWithWeight<Self, F, Self::Item, W>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> W,
+        W: Weight,
+    {
+        MinBatchWithWeight::new(self, min_batch_weight, count_fn)
+    }
+
+    fn min_batch_with_weight_capped<F, W>(
+        self,
+        min_batch_weight: W,
+        max_batch_weight: W,
+        count_fn: F,
+    ) -> MinBatchWithWeight<Self, F, Self::Item, W>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> W,
+        W: Weight,
+    {
+        MinBatchWithWeight::new_capped(self, min_batch_weight, max_batch_weight, count_fn)
+    }
+
+    fn min_batch_zip<O, FA, FB>(
+        self,
+        other: O,
+        min_batch_weight_a: usize,
+        count_a: FA,
+        min_batch_weight_b: usize,
+        count_b: FB,
+    ) -> MinBatchZip<Self, O, FA, FB, Self::Item, O::Item>
+    where
+        Self: Sized,
+        O: Stream,
+        FA: FnMut(&Self::Item) -> usize,
+        FB: FnMut(&O::Item) -> usize,
+    {
+        MinBatchZip::new(
+            self,
+            other,
+            min_batch_weight_a,
+            count_a,
+            min_batch_weight_b,
+            count_b,
+        )
+    }
+
+    fn rebatch<F, T>(self, min_batch_weight: usize, count_fn: F) -> Rebatch<Self, F, T>
+    where
+        Self: Sized + Stream<Item = Vec<T>>,
+        F: FnMut(&T) -> usize,
+    {
+        Rebatch::new(self, min_batch_weight, count_fn)
+    }
+
+    fn take_batches<F>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        n: usize,
+    ) -> TakeBatches<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        TakeBatches::new(self, min_batch_weight, count_fn, n)
+    }
+
+    /// Like [`min_batch_results`](Self::min_batch_results) but `policy` decides whether an `Err`
+    /// terminates the stream or batching goes on, see [`ErrorPolicy`].
+    fn try_min_batch<F, T, E>(
+        self,
+        min_batch_weight: usize,
+        count_fn: F,
+        policy: ErrorPolicy,
+    ) -> MinBatchResults<Self, F, T, E>
+    where
+        Self: Sized + Stream<Item = Result<T, E>>,
+        F: FnMut(&T) -> usize,
+    {
+        MinBatchResults::new_with_policy(self, min_batch_weight, count_fn, policy)
+    }
+}
+
+fn byte_len<T: AsRef<[u8]>>(item: &T) -> usize {
+    item.as_ref().len()
+}
+
+fn prekeyed_weight<T>(pair: &(T, usize)) -> usize {
+    pair.1
+}
+
+fn inline_size<T>(_item: &T) -> usize {
+    core::mem::size_of::<T>()
+}
+
+// Implement the trait for all types that implement Stream
+impl<T: ?Sized> MinBatchExt for T where T: Stream {}
+
+// The adapters fuse their inner stream themselves, so they are fused whatever `S` is.
+impl<S, F, T, C> FusedStream for MinBatch<S, F, T, C>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    C: BatchSink<T>,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, T, W> FusedStream for MinBatchWithWeight<S, F, T, W>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> W,
+    W: Weight,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S, F, T> FusedStream for MinBatchWithTimeout<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, T, E> FusedStream for MinBatchTry<S, F, T, E>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> Result<usize, E>,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty() && self.error.is_none()
+    }
+}
+
+impl<S, KF, F, T, K> FusedStream for MinBatchByKey<S, KF, F, T, K>
+where
+    S: Stream<Item = T>,
+    KF: Fn(&T) -> K,
+    K: PartialEq,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, T, E> FusedStream for MinBatchResults<S, F, T, E>
+where
+    S: Stream<Item = Result<T, E>>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.done
+            || (self.stream.is_terminated() && self.core.items.is_empty() && self.error.is_none())
+    }
+}
+
+impl<S, F, T> FusedStream for MinBatchIndexed<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T, usize) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, T> FusedStream for MinBatchWithStats<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, T, A, G> FusedStream for MinBatchFold<S, F, T, A, G>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    A: Clone,
+    G: Fn(A, T) -> A,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.acc.is_none()
+    }
+}
+
+impl<S, F, P, T> FusedStream for MinBatchLookahead<S, F, P, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(usize, usize) -> bool,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, P, T> FusedStream for MinBatchSplit<S, F, P, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(T) -> Vec<T>,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty() && self.pieces.is_empty()
+    }
+}
+
+impl<S, F, Fut, T> FusedStream for MinBatchAsyncWeight<S, F, Fut, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> Fut,
+    Fut: Future<Output = usize>,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty() && self.weighed_item.is_none()
+    }
+}
+
+impl<S, F, T> FusedStream for MinBatchTagged<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S, F, T> FusedStream for MinBatchDeadline<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, P, T> FusedStream for MinBatchWhen<S, F, P, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(&[T], usize) -> bool,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, T> FusedStream for MinBatchExclusive<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, T> FusedStream for MinBatchTarget<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S, F, T> FusedStream for MinBatchWithHeartbeat<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, T: ?Sized> FusedStream for MinBatchRef<S, F, T>
+where
+    S: Stream,
+    S::Item: Deref<Target = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S, F, T> FusedStream for MinBatchRateLimited<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, T> FusedStream for MinBatchEnumerate<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.batches.is_terminated()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S, F, T> FusedStream for MinBatchCancellable<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.done || (self.stream.is_terminated() && self.core.items.is_empty())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S, F, T> FusedStream for MinBatchConfigured<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, T> FusedStream for MinBatchWithCount<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.batches.is_terminated()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<S, G, R> FusedStream for DispatchRayon<S, G, R>
+where
+    S: Stream,
+    S::Item: Send + 'static,
+    G: Fn(S::Item) -> R + Send + Sync + 'static,
+    R: Send + 'static,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.in_flight.is_empty()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S, F, T> FusedStream for MinBatchBoxed<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.batches.is_terminated()
+    }
+}
+
+impl<S, F, T> FusedStream for MinBatchAbsorbTail<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty() && self.previous.is_none()
+    }
+}
+
+impl<S, F, O, T> FusedStream for MinBatchObserved<S, F, O, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    O: Fn(usize, usize),
+{
+    fn is_terminated(&self) -> bool {
+        self.batches.is_terminated()
+    }
+}
+
+impl<S, F, T> FusedStream for MinBatch2d<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S, F, T> FusedStream for MinBatchPooled<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S, F, G, Fut, T> FusedStream for MinBatchOrderedParallel<S, F, G, Fut, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    G: Fn(Vec<T>) -> Fut,
+    Fut: Future,
+{
+    fn is_terminated(&self) -> bool {
+        self.batches.is_done() && self.in_flight.is_empty() && self.reorder.is_empty()
+    }
+}
+
+impl<S, F, P, T> FusedStream for MinBatchWithBarriers<S, F, P, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(&T) -> bool,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, A, T> FusedStream for MinBatchAdaptive<S, F, A, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    A: Fn(usize, usize) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S, F, T> FusedStream for MinBatchDedup<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    T: Hash + Eq + Clone,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, T> FusedStream for MinBatchNonZero<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> NonZeroUsize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, T> FusedStream for MinBatchShared<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.batches.is_terminated()
+    }
+}
+
+impl<S, F, O, T> FusedStream for MinBatchInspect<S, F, O, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    O: Fn(&[T], usize),
+{
+    fn is_terminated(&self) -> bool {
+        self.batches.is_terminated()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S, F, K, V> FusedStream for MinBatchGrouped<S, F, K, V>
+where
+    S: Stream<Item = (K, V)>,
+    F: FnMut(&(K, V)) -> usize,
+    K: Hash + Eq,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.buffered == 0
+    }
+}
+
+impl<S, F, P, T> FusedStream for MinBatchUntil<S, F, P, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(&[T]) -> bool,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, T> FusedStream for MinBatchWithHistogram<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.batches.is_terminated()
+    }
+}
+
+impl<S, F, G, T, U> FusedStream for MinBatchThenMap<S, F, G, T, U>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    G: Fn(Vec<T>) -> U,
+{
+    fn is_terminated(&self) -> bool {
+        self.batches.is_terminated()
+    }
+}
+
+impl<S, F, T, C> FusedStream for MinBatchCollect<S, F, T, C>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    C: Default + Extend<T>,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.buffered == 0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S, F, T> FusedStream for MinBatchCatching<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize + UnwindSafe,
+{
+    fn is_terminated(&self) -> bool {
+        self.done
+            || (self.stream.is_terminated()
+                && self.core.items.is_empty()
+                && self.panicked.is_none())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, F> FusedStream for MinBatchEagerPull<T, F>
+where
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S, F, T> FusedStream for MinBatchTimestamped<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.batches.is_terminated()
+    }
+}
+
+impl<S, T> FusedStream for MinBatchPrekeyed<S, T>
+where
+    S: Stream<Item = (T, usize)>,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, D, T> FusedStream for MinBatchWithDropHandler<S, F, D, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    D: FnOnce(Vec<T>),
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, T> FusedStream for Rebatch<S, F, T>
+where
+    S: Stream<Item = Vec<T>>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.items.is_empty() && self.rest.is_empty()
+    }
+}
+
+impl<S, O, FA, FB, A, B> FusedStream for MinBatchZip<S, O, FA, FB, A, B>
+where
+    S: Stream<Item = A>,
+    O: Stream<Item = B>,
+    FA: FnMut(&A) -> usize,
+    FB: FnMut(&B) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.left.is_done()
+            && self.right.is_done()
+            && self.left_batch.is_none()
+            && self.right_batch.is_none()
+    }
+}
+
+impl<S, F, T> FusedStream for MinBatchWarmup<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S, F, T> FusedStream for MinBatchReporting<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.batches.is_terminated()
+    }
+}
+
+impl<S, F, T> FusedStream for MinBatchUnpin<S, F, T>
+where
+    S: Stream<Item = T> + Unpin,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, T> FusedStream for TakeBatches<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.done || (self.stream.is_terminated() && self.core.items.is_empty())
+    }
+}
+
+impl<S, F, T> FusedStream for MinBatchFilter<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> Option<usize>,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, P, T> FusedStream for MinBatchOnSentinel<S, F, P, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(&T) -> bool,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S, F, T> FusedStream for MinBatchDeadlineTagged<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.batches.is_terminated()
+    }
+}
+
+impl<S, F, T> FusedStream for MinBatchDecayed<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, T> FusedStream for MinBatchCredited<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
+
+impl<S, F, T> FusedStream for MinBatchSliding<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    T: Clone,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.core.items.is_empty()
+    }
+}
 
-impl<S: FusedStream, F, T> FusedStream for MinBatch<S, F, T>
+impl<S, F, KF, T, K> FusedStream for MinBatchSortedByKey<S, F, KF, T, K>
 where
     S: Stream<Item = T>,
-    F: Fn(&T) -> usize,
+    F: FnMut(&T) -> usize,
+    KF: Fn(&T) -> K,
+    K: Ord,
 {
     fn is_terminated(&self) -> bool {
-        self.stream.is_terminated() && self.items.is_empty()
+        self.batches.is_terminated()
     }
 }
 
-impl<S: FusedStream, F, T> FusedStream for MinBatchWithWeight<S, F, T>
+impl<S, F, T> FusedStream for MinBatchRanged<S, F, T>
 where
     S: Stream<Item = T>,
-    F: Fn(&T) -> usize,
+    F: FnMut(&T) -> usize,
 {
     fn is_terminated(&self) -> bool {
-        self.stream.is_terminated() && self.items.is_empty()
+        self.batches.is_terminated()
     }
 }
@@ -0,0 +1,31 @@
+use core::future::Future;
+use futures::stream::Stream;
+use futures::StreamExt;
+
+/// Runs `f` on every batch of `batches`, with up to `concurrency` of the returned futures
+/// spawned on the current tokio runtime at once, so they can make progress on different worker
+/// threads. Completes once every batch has been processed. A `concurrency` of `0` behaves like
+/// `1`.
+///
+/// # Panics
+///
+/// Must be called from within a tokio runtime. A panic inside a spawned future is propagated to
+/// the caller.
+pub async fn for_each_batch_parallel<S, G, Fut>(batches: S, concurrency: usize, f: G)
+where
+    S: Stream,
+    G: Fn(S::Item) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    batches
+        .map(|batch| tokio::spawn(f(batch)))
+        .buffer_unordered(concurrency.max(1))
+        .for_each(|result| async move {
+            if let Err(err) = result {
+                if err.is_panic() {
+                    std::panic::resume_unwind(err.into_panic());
+                }
+            }
+        })
+        .await
+}
@@ -65,6 +65,52 @@
 //! }
 //! ```
 //!
+//! ## no_std
+//!
+//! Disabling the default `std` feature in favor of `alloc` makes the crate `no_std` compatible,
+//! only the adapters built on top of tokio are unavailable then.
+//!
+//! ```rust
+//! extern crate alloc;
+//!
+//! use alloc::vec::Vec;
+//! use futures::stream::{self, StreamExt};
+//! use min_batch::ext::MinBatchExt;
+//!
+//! async fn batch_readings(readings: Vec<u16>) -> Vec<Vec<u16>> {
+//!     stream::iter(readings)
+//!         .min_batch(100, |reading: &u16| *reading as usize)
+//!         .collect()
+//!         .await
+//! }
+//! ```
+//!
+//! ## async-closure
+//!
+//! The `async-closure` feature adds `min_batch_async`, which weighs items by an async closure
+//! and therefore needs Rust 1.85 or later. The rest of the crate builds on older toolchains.
+//!
+//! ## test-util
+//!
+//! The `test-util` feature exposes `test_util::collect_now`, which synchronously drains the
+//! batches an adapter over an already populated source has ready, without an async runtime.
+//!
+//! ## debug-invariants
+//!
+//! The `debug-invariants` feature makes `MinBatch` and `MinBatchWithWeight` `debug_assert!` that
+//! no batch but the terminal partial one is yielded under `min_batch_weight`, unless a cap cut it.
+//!
+//! ## drop-warning
+//!
+//! The `drop-warning` feature warns whenever an adapter is dropped, e.g. cancelled mid-stream,
+//! with items still buffered, which are lost. The warning goes to stderr unless
+//! `drop_warning::set_drop_hook` routes it elsewhere, e.g. to `tracing`. Only `MinBatchFold`,
+//! which folds its items away, and `Rebatch`, which keeps its own buffer, never warn, and neither
+//! does `MinBatchWithDropHandler`, whose handler receives the buffered items instead.
+
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+
+extern crate alloc;
 
 #[cfg(test)]
 #[macro_use]
@@ -73,6 +119,105 @@ extern crate doc_comment;
 #[cfg(test)]
 doctest!("../README.md");
 
+pub mod batch_sink;
+mod batcher;
+#[cfg(feature = "std")]
+pub mod builder;
+pub mod collect_batches_into;
+#[cfg(feature = "rayon")]
+pub mod dispatch_rayon;
+#[cfg(feature = "drop-warning")]
+pub mod drop_warning;
 pub mod ext;
+#[cfg(feature = "std")]
+pub mod for_each_batch_parallel;
+pub mod mem_size;
 pub mod min_batch;
+pub mod min_batch_2d;
+pub mod min_batch_absorb_tail;
+pub mod min_batch_adaptive;
+#[cfg(feature = "async-closure")]
+pub mod min_batch_async;
+pub mod min_batch_async_weight;
+#[cfg(feature = "alloc")]
+pub mod min_batch_boxed;
+pub mod min_batch_by_key;
+#[cfg(feature = "std")]
+pub mod min_batch_cancellable;
+#[cfg(feature = "std")]
+pub mod min_batch_catching;
+pub mod min_batch_collect;
+pub mod min_batch_credited;
+#[cfg(feature = "std")]
+pub mod min_batch_deadline;
+#[cfg(feature = "std")]
+pub mod min_batch_deadline_tagged;
+pub mod min_batch_decayed;
+#[cfg(feature = "std")]
+pub mod min_batch_dedup;
+#[cfg(feature = "std")]
+pub mod min_batch_eager_pull;
+pub mod min_batch_enumerate;
+pub mod min_batch_exclusive;
+pub mod min_batch_filter;
+pub mod min_batch_fold;
+#[cfg(feature = "std")]
+pub mod min_batch_for_cpus;
+#[cfg(feature = "std")]
+pub mod min_batch_grouped;
+pub mod min_batch_indexed;
+pub mod min_batch_inspect;
+pub mod min_batch_lookahead;
+pub mod min_batch_nonzero;
+pub mod min_batch_observed;
+pub mod min_batch_on_sentinel;
+#[cfg(feature = "alloc")]
+pub mod min_batch_ordered_parallel;
+#[cfg(feature = "std")]
+pub mod min_batch_pooled;
+pub mod min_batch_prekeyed;
+pub mod min_batch_ranged;
+#[cfg(feature = "std")]
+pub mod min_batch_rate_limited;
+pub mod min_batch_ref;
+#[cfg(feature = "std")]
+pub mod min_batch_reporting;
+pub mod min_batch_results;
+pub mod min_batch_shared;
+pub mod min_batch_sliding;
+pub mod min_batch_sorted_by_key;
+pub mod min_batch_split;
+pub mod min_batch_tagged;
+pub mod min_batch_target;
+pub mod min_batch_then_map;
+#[cfg(feature = "std")]
+pub mod min_batch_timestamped;
+#[cfg(feature = "std")]
+pub mod min_batch_to_sender;
+pub mod min_batch_try;
+pub mod min_batch_unpin;
+pub mod min_batch_until;
+pub mod min_batch_warmup;
+pub mod min_batch_when;
+pub mod min_batch_with_barriers;
+pub mod min_batch_with_count;
+pub mod min_batch_with_drop_handler;
+#[cfg(feature = "std")]
+pub mod min_batch_with_heartbeat;
+pub mod min_batch_with_histogram;
+pub mod min_batch_with_stats;
+#[cfg(feature = "std")]
+pub mod min_batch_with_timeout;
 pub mod min_batch_with_weight;
+pub mod min_batch_zip;
+pub mod rebatch;
+pub mod sync;
+pub mod take_batches;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+pub mod weight;
+
+pub use sync::split_by_weight;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,76 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem::size_of;
+
+/// The memory an item occupies, its inline size plus the heap memory it owns, used by
+/// [`min_batch_by_mem_size`](crate::ext::MinBatchExt::min_batch_by_mem_size). Owned buffers
+/// count with their whole capacity, as that is what they keep allocated.
+pub trait MemSize {
+    fn mem_size(&self) -> usize;
+}
+
+macro_rules! inline_mem_size {
+    ($($t:ty),*) => {
+        $(
+            impl MemSize for $t {
+                fn mem_size(&self) -> usize {
+                    size_of::<$t>()
+                }
+            }
+        )*
+    };
+}
+
+inline_mem_size!(
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    bool,
+    char,
+    ()
+);
+
+impl MemSize for String {
+    fn mem_size(&self) -> usize {
+        size_of::<String>().saturating_add(self.capacity())
+    }
+}
+
+impl<T: MemSize> MemSize for Vec<T> {
+    fn mem_size(&self) -> usize {
+        let spare = (self.capacity() - self.len()).saturating_mul(size_of::<T>());
+        self.iter()
+            .fold(size_of::<Vec<T>>().saturating_add(spare), |size, item| {
+                size.saturating_add(item.mem_size())
+            })
+    }
+}
+
+impl<T: MemSize> MemSize for Box<T> {
+    fn mem_size(&self) -> usize {
+        size_of::<Box<T>>().saturating_add(self.as_ref().mem_size())
+    }
+}
+
+impl<T: MemSize> MemSize for Option<T> {
+    fn mem_size(&self) -> usize {
+        match self {
+            // the payload is stored inline, its heap memory comes on top
+            Some(value) => size_of::<Option<T>>()
+                .saturating_add(value.mem_size().saturating_sub(size_of::<T>())),
+            None => size_of::<Option<T>>(),
+        }
+    }
+}
@@ -1,74 +1,294 @@
+use alloc::vec::Vec;
+use core::fmt;
 use core::pin::Pin;
 use core::task::{Context, Poll};
-use futures::ready;
+use futures::sink::Sink;
 use futures::stream::{Fuse, Stream};
 use futures::StreamExt;
 use pin_project_lite::pin_project;
 
+use crate::batch_sink::BatchSink;
+use crate::batcher::Batcher;
+
+/// Upper bound of the capacity reserved up front for a batch, so that a huge `min_batch_weight`
+/// does not make the adapters try to allocate a buffer of that many items.
+const MAX_INITIAL_CAPACITY: usize = 1024;
+
+/// Capacity reserved for a new batch. Assuming items weigh about `1`, a batch needs about
+/// `min_batch_weight` items.
+pub(crate) fn initial_capacity(min_batch_weight: usize) -> usize {
+    min_batch_weight.min(MAX_INITIAL_CAPACITY)
+}
+
+/// Capacity reserved for a new batch that may be capped at `max_batch_len` items.
+fn reserve_len(min_batch_weight: usize, max_batch_len: Option<usize>) -> usize {
+    max_batch_len.map_or(initial_capacity(min_batch_weight), |max_len| {
+        max_len.min(initial_capacity(min_batch_weight))
+    })
+}
+
+/// The weight function of adapters that weigh items on their own, like
+/// [`min_batch_bytes`](crate::ext::MinBatchExt::min_batch_bytes).
+pub type WeightFn<T> = fn(&T) -> usize;
+
 pin_project! {
+    /// Batches the items of a stream until a batch reaches `min_batch_weight`, see
+    /// [`MinBatch::new`].
+    ///
+    /// The batch in progress lives in the adapter, so dropping the adapter between polls, e.g.
+    /// when a `select!` or a timeout cancels the task polling it, drops the buffered items with
+    /// it. Use [`take_pending`](Self::take_pending) beforehand or
+    /// [`min_batch_with_drop_handler`](crate::ext::MinBatchExt::min_batch_with_drop_handler) to
+    /// keep them. The `drop-warning` feature reports how many items were lost this way, see
+    /// [`set_drop_hook`](crate::drop_warning::set_drop_hook).
     #[must_use = "streams do nothing unless polled"]
-    #[derive(Debug)]
-    pub struct MinBatch<S, F, T> where
+    pub struct MinBatch<S, F, T, C = Vec<T>> where
     S: Stream<Item = T>,
-    F: Fn(&T) -> usize,
+    F: FnMut(&T) -> usize,
+    C: BatchSink<T>,
 {
         #[pin]
         pub(crate) stream: Fuse<S>,
-        current_batch_weight: usize,
-        pub(crate) items: Vec<S::Item>,
-        min_batch_weight: usize,
-        count_fn: F,
+        pub(crate) core: Batcher<F, C, usize>,
     }
 }
+
 impl<S, F, T> MinBatch<S, F, T>
 where
     S: Stream<Item = T>,
-    F: Fn(&T) -> usize,
+    F: FnMut(&T) -> usize,
 {
+    /// Creates an adapter yielding batches of at least `min_batch_weight`. A `min_batch_weight`
+    /// of `0` is met by any single item, so every item is yielded as a batch of its own, even if
+    /// it weighs `0`. Batches are never empty.
+    ///
+    /// Weights are accumulated with `saturating_add`, a batch whose weight saturates at
+    /// `usize::MAX` meets any threshold and is flushed instead of overflowing.
+    ///
+    /// Items weighing `0`, like empty payloads weighed by their byte length, make no progress,
+    /// so a stream of them is buffered until it ends. Use
+    /// [`min_batch_nonzero`](crate::ext::MinBatchExt::min_batch_nonzero) to rule them out by type
+    /// or [`flush_on_zero_progress_after`](Self::flush_on_zero_progress_after) to bound them.
     pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatch::new_in(stream, min_batch_weight, count_fn)
+    }
+
+    /// Like [`MinBatch::new`] but a batch is also yielded as soon as it holds `max_batch_len`
+    /// items, even if `min_batch_weight` has not been reached yet.
+    ///
+    /// The weight threshold is checked first, so an item that completes the weight is always
+    /// flushed as a regular weight batch. Items are never split, hence when `max_batch_len` is
+    /// smaller than the number of items needed to reach `min_batch_weight`, every batch is cut by
+    /// length and stays under weight. A `max_batch_len` of `0` behaves like `1`.
+    pub fn new_capped(
+        stream: S,
+        min_batch_weight: usize,
+        max_batch_len: usize,
+        count_fn: F,
+    ) -> Self {
+        MinBatch::new_capped_in(stream, min_batch_weight, max_batch_len, count_fn)
+    }
+}
+
+impl<S, F, T, C> MinBatch<S, F, T, C>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    C: BatchSink<T>,
+{
+    /// Like [`MinBatch::new`] but batches are accumulated into and yielded as `C`.
+    pub fn new_in(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
         MinBatch {
             stream: stream.fuse(),
-            current_batch_weight: 0,
-            items: Vec::with_capacity(min_batch_weight),
-            min_batch_weight,
-            count_fn,
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(reserve_len(min_batch_weight, None)),
         }
     }
+
+    /// Like [`MinBatch::new_capped`] but batches are accumulated into and yielded as `C`.
+    pub fn new_capped_in(
+        stream: S,
+        min_batch_weight: usize,
+        max_batch_len: usize,
+        count_fn: F,
+    ) -> Self {
+        let max_batch_len = Some(max_batch_len.max(1));
+        let mut core = Batcher::new(min_batch_weight, count_fn)
+            .reserving(reserve_len(min_batch_weight, max_batch_len));
+        core.max_batch_len = max_batch_len;
+        MinBatch {
+            stream: stream.fuse(),
+            core,
+        }
+    }
+
+    /// Forces a flush once `n` consecutive items added no weight to the batch in progress. Without
+    /// it, a `count_fn` returning `0` for every item never reaches `min_batch_weight` and the whole
+    /// stream is buffered until it ends. An `n` of `0` behaves like `1`.
+    pub fn flush_on_zero_progress_after(mut self, n: usize) -> Self {
+        self.core.zero_progress_limit = Some(n.max(1));
+        self
+    }
+
+    /// Discards the terminal partial batch instead of yielding it: once the inner stream ends, the
+    /// items buffered under `min_batch_weight` are dropped and the stream ends right away. This
+    /// loses data, use it only when the remainder is picked up elsewhere, e.g. by the next window
+    /// of a streaming job. Batches cut by `max_batch_len` or by
+    /// [`flush_on_zero_progress_after`](Self::flush_on_zero_progress_after) are still yielded.
+    pub fn drop_incomplete_tail(mut self) -> Self {
+        self.core.drop_incomplete_tail = true;
+        self
+    }
+
+    /// Yields the batch in progress as soon as the inner stream has no item ready, i.e. returns
+    /// `Poll::Pending`, instead of waiting for `min_batch_weight`. Batches get lighter but items
+    /// wait less, batching only what arrives in a burst. The inner stream has registered the
+    /// waker before returning `Pending`, so the adapter is still woken up for the next item.
+    pub fn eager_flush(mut self) -> Self {
+        self.core.eager_flush = true;
+        self
+    }
+
+    /// Weight accumulated by the batch in progress.
+    pub fn current_weight(&self) -> usize {
+        self.core.current_batch_weight
+    }
+
+    /// Number of items buffered in the batch in progress.
+    pub fn pending_len(&self) -> usize {
+        self.core.buffered
+    }
+
+    /// Changes the weight threshold for the batch in progress and all the following ones. Lowering
+    /// it does not split the items already buffered: if they meet the new threshold, they are all
+    /// yielded as a single batch on the next poll.
+    pub fn set_min_batch_weight(&mut self, min_batch_weight: usize) {
+        self.core.min_batch_weight = min_batch_weight;
+        self.core.reserve_len = reserve_len(min_batch_weight, self.core.max_batch_len);
+    }
+
+    /// Takes the batch in progress out of the adapter, the next poll starts a fresh batch.
+    /// Returns `None` if nothing is buffered.
+    pub fn take_pending(&mut self) -> Option<C> {
+        self.core.take_pending().map(|(items, _)| items)
+    }
+
+    /// Discards the batch in progress, e.g. when upstream signals that partial work is to be
+    /// abandoned. The buffered items are dropped, the inner stream is left untouched and the next
+    /// poll starts a fresh batch.
+    pub fn reset(&mut self) {
+        self.core.reset();
+    }
+
+    /// Acquires a reference to the underlying stream.
+    pub fn get_ref(&self) -> &S {
+        self.stream.get_ref()
+    }
+
+    /// Acquires a mutable reference to the underlying stream.
+    ///
+    /// It is inadvisable to directly poll the underlying stream, as it bypasses the batching.
+    pub fn get_mut(&mut self) -> &mut S {
+        self.stream.get_mut()
+    }
+
+    /// Acquires a pinned mutable reference to the underlying stream.
+    ///
+    /// It is inadvisable to directly poll the underlying stream, as it bypasses the batching.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut S> {
+        self.project().stream.get_pin_mut()
+    }
+
+    /// Consumes this adapter, returning the underlying stream. The items of the batch in progress
+    /// are dropped, use [`take_pending`](Self::take_pending) first to keep them.
+    pub fn into_inner(self) -> S {
+        self.stream.into_inner()
+    }
 }
 
-impl<S, F, T> Stream for MinBatch<S, F, T>
+/// Shows the state of the batch in progress, neither the inner stream nor the closure need to
+/// implement `Debug`.
+impl<S, F, T, C> fmt::Debug for MinBatch<S, F, T, C>
 where
     S: Stream<Item = T>,
-    F: Fn(&T) -> usize,
+    F: FnMut(&T) -> usize,
+    C: BatchSink<T>,
 {
-    type Item = Vec<S::Item>;
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MinBatch")
+            .field("min_batch_weight", &self.core.min_batch_weight)
+            .field("max_batch_len", &self.core.max_batch_len)
+            .field("current_batch_weight", &self.core.current_batch_weight)
+            .field("pending_len", &self.core.items.len())
+            .finish_non_exhaustive()
+    }
+}
 
-    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let mut me = self.project();
-        loop {
-            match ready!(me.stream.as_mut().poll_next(cx)) {
-                Some(item) => {
-                    if me.items.is_empty() {
-                        me.items.reserve(*me.min_batch_weight);
-                    }
-                    let new_count = (me.count_fn)(&item);
-                    me.items.push(item);
-                    *me.current_batch_weight += new_count;
-                    if me.current_batch_weight >= me.min_batch_weight {
-                        *me.current_batch_weight = 0;
-                        return Poll::Ready(Some(std::mem::take(me.items)));
-                    }
-                }
-                None => {
-                    let last = if me.items.is_empty() {
-                        None
-                    } else {
-                        *me.current_batch_weight = 0;
-                        Some(std::mem::take(me.items))
-                    };
-                    return Poll::Ready(last);
-                }
-            }
+/// Clones the inner stream, the closure and the batch in progress. The cloned inner stream is
+/// fused anew, so a clone of an adapter whose upstream has already finished polls it again.
+impl<S, F, T, C> Clone for MinBatch<S, F, T, C>
+where
+    S: Stream<Item = T> + Clone,
+    F: FnMut(&T) -> usize + Clone,
+    C: BatchSink<T> + Clone,
+{
+    fn clone(&self) -> Self {
+        MinBatch {
+            stream: self.stream.get_ref().clone().fuse(),
+            core: self.core.clone(),
         }
     }
 }
+
+impl<S, F, T, C> Stream for MinBatch<S, F, T, C>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    C: BatchSink<T>,
+{
+    type Item = C;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.project();
+        me.core
+            .poll_next(me.stream, cx)
+            .map(|batch| batch.map(|(items, _)| items))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending_batch = usize::from(!self.core.items.is_empty());
+        let upper = self
+            .stream
+            .size_hint()
+            .1
+            .and_then(|upper| upper.checked_add(pending_batch));
+        (0, upper)
+    }
+}
+
+/// Forwards to the inner stream when it is a sink as well, so that a duplex connection stays
+/// usable for the other direction once its incoming half is batched.
+impl<S, F, T, C, Item> Sink<Item> for MinBatch<S, F, T, C>
+where
+    S: Stream<Item = T> + Sink<Item>,
+    F: FnMut(&T) -> usize,
+    C: BatchSink<T>,
+{
+    type Error = S::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().stream.poll_ready(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error> {
+        self.project().stream.start_send(item)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().stream.poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().stream.poll_close(cx)
+    }
+}
@@ -0,0 +1,70 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches items until a batch reaches both `min_batch_weight` and `min_batch_len` items.
+    /// Unlike the capped variants, which bound batches from above, both thresholds are lower
+    /// bounds that have to be met together. When the stream ends, whatever is left is yielded
+    /// regardless of either threshold.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatch2d<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        min_batch_len: usize,
+    }
+}
+
+impl<S, F, T> MinBatch2d<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, min_batch_len: usize, count_fn: F) -> Self {
+        MinBatch2d {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_len)),
+            min_batch_len,
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatch2d<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let weight = (me.core.count_fn)(&item);
+                    me.core.add(item, weight);
+                    if me.core.current_batch_weight >= me.core.min_batch_weight
+                        && me.core.buffered >= *me.min_batch_len
+                    {
+                        return Poll::Ready(Some(me.core.flush().0));
+                    }
+                }
+                // the batch may be over weight already, only short of items
+                None => return Poll::Ready(me.core.take_pending().map(|(items, _)| items)),
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but when the stream ends with a
+    /// partial batch weighing less than `min_tail_weight`, its items are appended to the
+    /// previous batch instead of being yielded as a tiny batch of their own. To be able to do so,
+    /// every full batch is held back until the next one is complete or the stream ends, which
+    /// delays each batch by one.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchAbsorbTail<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        pub(crate) previous: Option<Vec<S::Item>>,
+        min_tail_weight: usize,
+    }
+}
+
+impl<S, F, T> MinBatchAbsorbTail<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, min_tail_weight: usize, count_fn: F) -> Self {
+        MinBatchAbsorbTail {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            previous: None,
+            min_tail_weight,
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchAbsorbTail<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let weight = (me.core.count_fn)(&item);
+                    if let Some((full, _)) = me.core.push(item, weight) {
+                        if let Some(previous) = me.previous.replace(full) {
+                            return Poll::Ready(Some(previous));
+                        }
+                    }
+                }
+                None => {
+                    if let Some(mut previous) = me.previous.take() {
+                        if !me.core.items.is_empty()
+                            && me.core.current_batch_weight < *me.min_tail_weight
+                        {
+                            previous.append(&mut me.core.flush().0);
+                        }
+                        // a heavier tail stays buffered and is yielded on the next poll
+                        return Poll::Ready(Some(previous));
+                    }
+                    return Poll::Ready(me.core.finish().map(|(items, _)| items));
+                }
+            }
+        }
+    }
+}
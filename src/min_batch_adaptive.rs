@@ -0,0 +1,76 @@
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::{Context, Poll};
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but the threshold follows the
+    /// downstream `backlog`, e.g. the depth of a queue the batches are sent to. On every poll the
+    /// threshold is recomputed as `adjust_fn(base_weight, backlog)` before the batch in progress
+    /// is compared against it, so batches can grow while downstream is saturated and shrink
+    /// again once it catches up. A threshold lowered below the weight already buffered flushes
+    /// the batch on the next poll.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchAdaptive<S, F, A, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    A: Fn(usize, usize) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        base_weight: usize,
+        backlog: Arc<AtomicUsize>,
+        adjust_fn: A,
+    }
+}
+
+impl<S, F, A, T> MinBatchAdaptive<S, F, A, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    A: Fn(usize, usize) -> usize,
+{
+    pub fn new(
+        stream: S,
+        base_weight: usize,
+        count_fn: F,
+        backlog: Arc<AtomicUsize>,
+        adjust_fn: A,
+    ) -> Self {
+        MinBatchAdaptive {
+            stream: stream.fuse(),
+            core: Batcher::new(base_weight, count_fn),
+            base_weight,
+            backlog,
+            adjust_fn,
+        }
+    }
+}
+
+impl<S, F, A, T> Stream for MinBatchAdaptive<S, F, A, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    A: Fn(usize, usize) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.project();
+        let min_batch_weight = (me.adjust_fn)(*me.base_weight, me.backlog.load(Ordering::Relaxed));
+        me.core.min_batch_weight = min_batch_weight;
+        me.core.reserve_len = initial_capacity(min_batch_weight);
+        me.core
+            .poll_next(me.stream, cx)
+            .map(|batch| batch.map(|(items, _)| items))
+    }
+}
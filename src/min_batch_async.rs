@@ -0,0 +1,48 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::pin::Pin;
+use futures::stream::{self, Fuse, Stream};
+use futures::StreamExt;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+struct State<S, F, T> {
+    stream: Pin<Box<Fuse<S>>>,
+    core: Batcher<F, Vec<T>, usize>,
+}
+
+/// Batches `stream` like [`MinBatch`](crate::min_batch::MinBatch) but weighs items by an async
+/// closure, e.g. `async |item: &T| lookup_weight(item).await`. Unlike
+/// [`MinBatchAsyncWeight`](crate::min_batch_async_weight::MinBatchAsyncWeight), whose `count_fn`
+/// returns a future that can't borrow the item, the future of an [`AsyncFnMut`] borrows both the
+/// closure and the item while the weight is awaited, the item is pushed into the batch once the
+/// weight is known and the inner stream is not polled meanwhile.
+///
+/// The borrowing future can't be stored next to the items in a named adapter, so the batches are
+/// an opaque stream driving an `async` block, and `stream` is boxed once to be polled from it.
+pub fn min_batch_async<S, F>(
+    stream: S,
+    min_batch_weight: usize,
+    count_fn: F,
+) -> impl Stream<Item = Vec<S::Item>>
+where
+    S: Stream,
+    F: AsyncFnMut(&S::Item) -> usize,
+{
+    let state = State {
+        stream: Box::pin(stream.fuse()),
+        core: Batcher::new(min_batch_weight, count_fn)
+            .reserving(initial_capacity(min_batch_weight)),
+    };
+    stream::unfold(state, |mut state| async move {
+        while let Some(item) = state.stream.next().await {
+            let weight = (state.core.count_fn)(&item).await;
+            if let Some((items, _)) = state.core.push(item, weight) {
+                return Some((items, state));
+            }
+        }
+        let (items, _) = state.core.finish()?;
+        Some((items, state))
+    })
+}
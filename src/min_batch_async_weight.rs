@@ -0,0 +1,80 @@
+use alloc::vec::Vec;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but `count_fn` returns a future
+    /// resolving to the weight. The item is held aside while its weight future is in flight and
+    /// only pushed into the batch once the weight is known, the inner stream is not polled
+    /// meanwhile.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchAsyncWeight<S, F, Fut, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> Fut,
+    Fut: Future<Output = usize>,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        #[pin]
+        weight_fut: Option<Fut>,
+        pub(crate) weighed_item: Option<S::Item>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+    }
+}
+
+impl<S, F, Fut, T> MinBatchAsyncWeight<S, F, Fut, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> Fut,
+    Fut: Future<Output = usize>,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchAsyncWeight {
+            stream: stream.fuse(),
+            weight_fut: None,
+            weighed_item: None,
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+        }
+    }
+}
+
+impl<S, F, Fut, T> Stream for MinBatchAsyncWeight<S, F, Fut, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> Fut,
+    Fut: Future<Output = usize>,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            if let Some(weight_fut) = me.weight_fut.as_mut().as_pin_mut() {
+                let weight = ready!(weight_fut.poll(cx));
+                me.weight_fut.set(None);
+                if let Some(item) = me.weighed_item.take() {
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        return Poll::Ready(Some(items));
+                    }
+                }
+            }
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    me.weight_fut.set(Some((me.core.count_fn)(&item)));
+                    *me.weighed_item = Some(item);
+                }
+                None => return Poll::Ready(me.core.finish().map(|(items, _)| items)),
+            }
+        }
+    }
+}
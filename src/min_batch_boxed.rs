@@ -0,0 +1,54 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::Stream;
+use pin_project_lite::pin_project;
+
+use crate::min_batch::MinBatch;
+
+pin_project! {
+    /// Batches like [`MinBatch`] but yields every batch as a boxed slice, without the spare capacity
+    /// a `Vec` keeps after growing. Trimming it costs a reallocation per batch, which pays off when
+    /// batches are retained in memory for long, otherwise prefer [`MinBatch`].
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchBoxed<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) batches: MinBatch<S, F, T>,
+    }
+}
+
+impl<S, F, T> MinBatchBoxed<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchBoxed {
+            batches: MinBatch::new(stream, min_batch_weight, count_fn),
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchBoxed<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Box<[S::Item]>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project()
+            .batches
+            .poll_next(cx)
+            .map(|batch| batch.map(Vec::into_boxed_slice))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.batches.size_hint()
+    }
+}
@@ -0,0 +1,89 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches consecutive items sharing the same key. A batch is flushed when it reaches
+    /// `min_batch_weight` or when an item with a different key arrives, in which case that item
+    /// starts the next batch.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchByKey<S, KF, F, T, K> where
+    S: Stream<Item = T>,
+    KF: Fn(&T) -> K,
+    K: PartialEq,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        current_key: Option<K>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        key_fn: KF,
+    }
+}
+
+impl<S, KF, F, T, K> MinBatchByKey<S, KF, F, T, K>
+where
+    S: Stream<Item = T>,
+    KF: Fn(&T) -> K,
+    K: PartialEq,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, key_fn: KF, count_fn: F) -> Self {
+        MinBatchByKey {
+            stream: stream.fuse(),
+            current_key: None,
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            key_fn,
+        }
+    }
+}
+
+impl<S, KF, F, T, K> Stream for MinBatchByKey<S, KF, F, T, K>
+where
+    S: Stream<Item = T>,
+    KF: Fn(&T) -> K,
+    K: PartialEq,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            if let Some((items, _)) = me.core.take_full() {
+                *me.current_key = None;
+                return Poll::Ready(Some(items));
+            }
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let key = (me.key_fn)(&item);
+                    let key_changed = me.current_key.as_ref().is_some_and(|k| *k != key);
+                    let batch = if key_changed {
+                        Some(me.core.flush().0)
+                    } else {
+                        None
+                    };
+                    *me.current_key = Some(key);
+                    let weight = (me.core.count_fn)(&item);
+                    me.core.add(item, weight);
+                    if batch.is_some() {
+                        return Poll::Ready(batch);
+                    }
+                }
+                None => {
+                    *me.current_key = None;
+                    return Poll::Ready(me.core.finish().map(|(items, _)| items));
+                }
+            }
+        }
+    }
+}
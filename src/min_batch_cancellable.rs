@@ -0,0 +1,69 @@
+use alloc::vec::Vec;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+use tokio_util::sync::{CancellationToken, WaitForCancellationFutureOwned};
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) until `token` is cancelled. The
+    /// cancellation is checked before the inner stream on every poll, once it fires the pending
+    /// batch is yielded, if there is one, and the stream terminates without polling the inner
+    /// stream any further.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchCancellable<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        #[pin]
+        cancelled: WaitForCancellationFutureOwned,
+        pub(crate) done: bool,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+    }
+}
+
+impl<S, F, T> MinBatchCancellable<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, token: CancellationToken) -> Self {
+        MinBatchCancellable {
+            stream: stream.fuse(),
+            cancelled: token.cancelled_owned(),
+            done: false,
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchCancellable<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.project();
+        if *me.done {
+            return Poll::Ready(None);
+        }
+        if me.cancelled.poll(cx).is_ready() {
+            *me.done = true;
+            return Poll::Ready(me.core.take_pending().map(|(items, _)| items));
+        }
+        me.core
+            .poll_next(me.stream, cx)
+            .map(|batch| batch.map(|(items, _)| items))
+    }
+}
@@ -0,0 +1,105 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::any::Any;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+use std::panic::{self, AssertUnwindSafe, UnwindSafe};
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+/// The item `count_fn` panicked on, together with the panic payload.
+#[derive(Debug)]
+pub struct CountFnPanic<T> {
+    pub item: T,
+    pub payload: Box<dyn Any + Send>,
+}
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but catches a panic of `count_fn`
+    /// instead of unwinding through the task and losing the buffered items. The batch in progress
+    /// is yielded as `Ok` first, then a [`CountFnPanic`] with the offending item as `Err`, and then
+    /// the stream terminates. The panic hook still runs, so the panic is reported as usual.
+    ///
+    /// `count_fn` has to be `UnwindSafe`, so that the state it captures can't be observed half
+    /// updated by the caller after a panic. The adapter never calls it again once it panicked.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchCatching<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    F: UnwindSafe,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        pub(crate) panicked: Option<CountFnPanic<T>>,
+        pub(crate) done: bool,
+    }
+}
+
+impl<S, F, T> MinBatchCatching<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize + UnwindSafe,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchCatching {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            panicked: None,
+            done: false,
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchCatching<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize + UnwindSafe,
+{
+    type Item = Result<Vec<T>, CountFnPanic<T>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        if *me.done {
+            return Poll::Ready(None);
+        }
+        if let Some(panicked) = me.panicked.take() {
+            *me.done = true;
+            return Poll::Ready(Some(Err(panicked)));
+        }
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let count_fn = &mut me.core.count_fn;
+                    let weight = match panic::catch_unwind(AssertUnwindSafe(|| count_fn(&item))) {
+                        Ok(weight) => weight,
+                        Err(payload) => {
+                            let panicked = CountFnPanic { item, payload };
+                            return match me.core.take_pending() {
+                                Some((items, _)) => {
+                                    *me.panicked = Some(panicked);
+                                    Poll::Ready(Some(Ok(items)))
+                                }
+                                None => {
+                                    *me.done = true;
+                                    Poll::Ready(Some(Err(panicked)))
+                                }
+                            };
+                        }
+                    };
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        return Poll::Ready(Some(Ok(items)));
+                    }
+                }
+                None => return Poll::Ready(me.core.finish().map(|(items, _)| Ok(items))),
+            }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use core::iter;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batch_sink::BatchSink;
+use crate::batcher::Batcher;
+
+/// Gives any `Default + Extend` collection the [`BatchSink`] interface, counting its items itself.
+#[derive(Debug, Default)]
+pub(crate) struct Extending<C> {
+    collection: C,
+    len: usize,
+}
+
+impl<T, C> BatchSink<T> for Extending<C>
+where
+    C: Default + Extend<T>,
+{
+    fn push(&mut self, item: T) {
+        self.collection.extend(iter::once(item));
+        self.len += 1;
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but builds every batch as any
+    /// `C: Default + Extend<T>`, e.g. a `String` out of `char`s or a `BTreeSet`, starting from
+    /// `C::default()` and extending it item by item. Unlike [`BatchSink`](crate::batch_sink::BatchSink)
+    /// containers, `C` can't tell how many items it holds, so there is no length cap.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchCollect<S, F, T, C> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    C: Default,
+    C: Extend<T>,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Extending<C>, usize>,
+    }
+}
+
+impl<S, F, T, C> MinBatchCollect<S, F, T, C>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    C: Default + Extend<T>,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchCollect {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn),
+        }
+    }
+}
+
+impl<S, F, T, C> Stream for MinBatchCollect<S, F, T, C>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    C: Default + Extend<T>,
+{
+    type Item = C;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let weight = (me.core.count_fn)(&item);
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        return Poll::Ready(Some(items.collection));
+                    }
+                }
+                None => return Poll::Ready(me.core.finish().map(|(items, _)| items.collection)),
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicIsize, Ordering};
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::task::AtomicWaker;
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+/// Credits of a credit-based flow control, shared between a
+/// [`MinBatchCredited`] pulling items and the downstream granting them.
+///
+/// The counter is an [`AtomicIsize`] just like a bare one, but it also keeps the waker of the
+/// stalled adapter, so that granting credits by [`add`](Self::add) resumes it.
+#[derive(Debug, Default)]
+pub struct Credits {
+    available: AtomicIsize,
+    waker: AtomicWaker,
+}
+
+impl Credits {
+    pub fn new(initial: isize) -> Self {
+        Credits {
+            available: AtomicIsize::new(initial),
+            waker: AtomicWaker::new(),
+        }
+    }
+
+    /// Grants `n` more credits and wakes up the adapter if it waits for them.
+    pub fn add(&self, n: isize) {
+        self.available.fetch_add(n, Ordering::AcqRel);
+        self.waker.wake();
+    }
+
+    /// The credits left, `0` or less when the adapter is stalled.
+    pub fn available(&self) -> isize {
+        self.available.load(Ordering::Acquire)
+    }
+
+    fn consume(&self) {
+        self.available.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but pulls from upstream only while
+    /// `credits` are positive, every item pulled consuming one of them. Once they are exhausted
+    /// the adapter stalls, keeping its batch in progress, until downstream grants more by
+    /// [`Credits::add`].
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchCredited<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        credits: Arc<Credits>,
+    }
+}
+
+impl<S, F, T> MinBatchCredited<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, credits: Arc<Credits>) -> Self {
+        MinBatchCredited {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            credits,
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchCredited<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            if me.credits.available() <= 0 {
+                me.credits.waker.register(cx.waker());
+                // credits granted before the waker was registered would not wake us up
+                if me.credits.available() <= 0 {
+                    return Poll::Pending;
+                }
+            }
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    me.credits.consume();
+                    let weight = (me.core.count_fn)(&item);
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        return Poll::Ready(Some(items));
+                    }
+                }
+                None => return Poll::Ready(me.core.finish().map(|(items, _)| items)),
+            }
+        }
+    }
+}
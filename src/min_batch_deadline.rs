@@ -0,0 +1,111 @@
+use alloc::vec::Vec;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+use std::time::Duration;
+use tokio::time::{sleep, Instant, Sleep};
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but also flushes the accumulated
+    /// items once `max_latency` has elapsed since the first item of the batch was pushed. Unlike
+    /// [`MinBatchWithTimeout`](crate::min_batch_with_timeout::MinBatchWithTimeout), following
+    /// items do not postpone the deadline, so no item waits longer than `max_latency` for its
+    /// batch, no matter how steadily a light stream trickles in.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchDeadline<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        #[pin]
+        deadline: Option<Sleep>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        max_latency: Duration,
+    }
+}
+
+impl<S, F, T> MinBatchDeadline<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, max_latency: Duration) -> Self {
+        MinBatchDeadline {
+            stream: stream.fuse(),
+            deadline: None,
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            max_latency,
+        }
+    }
+
+    /// Polls like [`poll_next`](Stream::poll_next) but tags every batch with whether the
+    /// deadline cut it, see
+    /// [`MinBatchDeadlineTagged`](crate::min_batch_deadline_tagged::MinBatchDeadlineTagged).
+    pub(crate) fn poll_tagged(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<(Vec<T>, bool)>> {
+        let mut me = self.project();
+        loop {
+            match me.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    if me.core.items.is_empty() {
+                        me.deadline.set(Some(sleep(*me.max_latency)));
+                    }
+                    let weight = (me.core.count_fn)(&item);
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        me.deadline.set(None);
+                        return Poll::Ready(Some((items, false)));
+                    }
+                    // a stream that is always ready never reaches the timer, so check it here too
+                    let expired = me
+                        .deadline
+                        .as_ref()
+                        .as_pin_ref()
+                        .is_some_and(|deadline| deadline.deadline() <= Instant::now());
+                    if expired {
+                        me.deadline.set(None);
+                        return Poll::Ready(Some((me.core.flush().0, true)));
+                    }
+                }
+                Poll::Ready(None) => {
+                    me.deadline.set(None);
+                    return Poll::Ready(me.core.finish().map(|(items, _)| (items, false)));
+                }
+                Poll::Pending => {
+                    if let Some(deadline) = me.deadline.as_mut().as_pin_mut() {
+                        if deadline.poll(cx).is_ready() {
+                            me.deadline.set(None);
+                            if let Some((items, _)) = me.core.take_pending() {
+                                return Poll::Ready(Some((items, true)));
+                            }
+                        }
+                    }
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchDeadline<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_tagged(cx)
+            .map(|batch| batch.map(|(items, _)| items))
+    }
+}
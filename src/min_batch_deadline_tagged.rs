@@ -0,0 +1,49 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::Stream;
+use pin_project_lite::pin_project;
+use std::time::Duration;
+
+use crate::min_batch_deadline::MinBatchDeadline;
+
+pin_project! {
+    /// Batches like [`MinBatchDeadline`](crate::min_batch_deadline::MinBatchDeadline), flushing
+    /// a batch once `max_assembly` has elapsed since its first item was pushed, and yields every
+    /// batch tagged with whether it was cut by that deadline, so that downstream can react to
+    /// batches assembled too slowly. Batches that reached `min_batch_weight` and the terminal
+    /// partial batch are tagged `false`.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchDeadlineTagged<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) batches: MinBatchDeadline<S, F, T>,
+    }
+}
+
+impl<S, F, T> MinBatchDeadlineTagged<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, max_assembly: Duration) -> Self {
+        MinBatchDeadlineTagged {
+            batches: MinBatchDeadline::new(stream, min_batch_weight, count_fn, max_assembly),
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchDeadlineTagged<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = (Vec<S::Item>, bool);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().batches.poll_tagged(cx)
+    }
+}
@@ -0,0 +1,106 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but the flush is decided on a
+    /// decayed sum modelling the recent load rather than on the plain sum of the weights: on
+    /// every push the accumulator becomes `acc * decay + weight` and the batch is yielded once
+    /// `acc >= threshold`, the accumulator starting over at `0` with the next batch.
+    ///
+    /// `decay` is meant to lie in `[0, 1)`, the weights of older items fading the faster the
+    /// lower it is, so a burst of heavy items flushes sooner than the same items spread among
+    /// light ones. A `decay` of `1.0` is the plain sum of [`MinBatch`](crate::min_batch::MinBatch).
+    ///
+    /// With a `decay` under `1.0` the accumulator is bounded: if no item weighs more than `w`, it
+    /// approaches but never exceeds `w / (1 - decay)`. A `threshold` above that limit is never
+    /// reached, so the whole stream is buffered until it ends and yielded as a single batch. Use
+    /// [`new_capped`](Self::new_capped) to bound the batches by their number of items then.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchDecayed<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        acc: f64,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        threshold: f64,
+        decay: f64,
+    }
+}
+
+impl<S, F, T> MinBatchDecayed<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, threshold: f64, decay: f64, count_fn: F) -> Self {
+        MinBatchDecayed {
+            stream: stream.fuse(),
+            acc: 0.0,
+            // the decayed accumulator alone decides when a batch is due
+            core: Batcher::new(usize::MAX, count_fn)
+                .reserving(initial_capacity(threshold as usize)),
+            threshold,
+            decay,
+        }
+    }
+
+    /// Like [`MinBatchDecayed::new`] but a batch is also yielded as soon as it holds
+    /// `max_batch_len` items, even if the accumulator is still under `threshold`. A
+    /// `max_batch_len` of `0` behaves like `1`.
+    pub fn new_capped(
+        stream: S,
+        threshold: f64,
+        decay: f64,
+        max_batch_len: usize,
+        count_fn: F,
+    ) -> Self {
+        let mut batches = MinBatchDecayed::new(stream, threshold, decay, count_fn);
+        batches.core.max_batch_len = Some(max_batch_len.max(1));
+        batches
+    }
+}
+
+impl<S, F, T> Stream for MinBatchDecayed<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let weight = (me.core.count_fn)(&item);
+                    me.core.add(item, weight);
+                    *me.acc = *me.acc * *me.decay + weight as f64;
+                    if *me.acc >= *me.threshold
+                        || me
+                            .core
+                            .max_batch_len
+                            .is_some_and(|max_len| me.core.buffered >= max_len)
+                    {
+                        *me.acc = 0.0;
+                        return Poll::Ready(Some(me.core.flush().0));
+                    }
+                }
+                None => {
+                    *me.acc = 0.0;
+                    return Poll::Ready(me.core.take_pending().map(|(items, _)| items));
+                }
+            }
+        }
+    }
+}
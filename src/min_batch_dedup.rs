@@ -0,0 +1,81 @@
+use alloc::vec::Vec;
+use core::hash::Hash;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+use std::collections::HashSet;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but drops an item equal to one
+    /// already buffered in the batch in progress. Only retained items are weighed and count
+    /// towards `min_batch_weight`. The set of seen items is reset at each batch boundary, so
+    /// duplicates across batches are kept.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchDedup<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    T: Hash,
+    T: Eq,
+    T: Clone,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        seen: HashSet<T>,
+    }
+}
+
+impl<S, F, T> MinBatchDedup<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    T: Hash + Eq + Clone,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchDedup {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            seen: HashSet::new(),
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchDedup<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    T: Hash + Eq + Clone,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    if me.seen.contains(&item) {
+                        continue;
+                    }
+                    let weight = (me.core.count_fn)(&item);
+                    me.seen.insert(item.clone());
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        me.seen.clear();
+                        return Poll::Ready(Some(items));
+                    }
+                }
+                None => {
+                    me.seen.clear();
+                    return Poll::Ready(me.core.finish().map(|(items, _)| items));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::Stream;
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+use tokio::sync::mpsc::{self, Receiver};
+use tokio::task::JoinHandle;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+/// Stops the pulling task along with the adapter, even while it awaits the upstream.
+#[derive(Debug)]
+struct AbortOnDrop(JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but the upstream is pulled by a task
+    /// spawned on the current tokio runtime, ahead of the polls of a slow consumer, into a bounded
+    /// buffer of `capacity` items. Once a batch reaches `min_batch_weight`, it also takes every item
+    /// buffered at that moment, so batches coalesce while the consumer lags behind and nothing is
+    /// dropped. Items the task pulls while the batch is taken wait for the next one.
+    ///
+    /// At most `capacity` items are buffered besides the batch in progress, when the buffer is full
+    /// the task stops pulling until the consumer catches up. Dropping the adapter stops the task.
+    /// A `capacity` of `0` behaves like `1`.
+    ///
+    /// # Panics
+    ///
+    /// Must be created from within a tokio runtime.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchEagerPull<T, F> where
+    F: FnMut(&T) -> usize,
+{
+        buffer: Receiver<T>,
+        _puller: AbortOnDrop,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        pub(crate) done: bool,
+    }
+}
+
+impl<T, F> MinBatchEagerPull<T, F>
+where
+    T: Send + 'static,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new<S>(stream: S, min_batch_weight: usize, count_fn: F, capacity: usize) -> Self
+    where
+        S: Stream<Item = T> + Send + 'static,
+    {
+        let (sender, buffer) = mpsc::channel(capacity.max(1));
+        let puller = tokio::spawn(async move {
+            let mut stream = core::pin::pin!(stream);
+            // reserving first keeps the upstream unpolled while the buffer is full
+            while let Ok(permit) = sender.reserve().await {
+                match stream.next().await {
+                    Some(item) => permit.send(item),
+                    None => break,
+                }
+            }
+        });
+        MinBatchEagerPull {
+            buffer,
+            _puller: AbortOnDrop(puller),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            done: false,
+        }
+    }
+}
+
+impl<T, F> Stream for MinBatchEagerPull<T, F>
+where
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.project();
+        if *me.done {
+            return Poll::Ready(None);
+        }
+        loop {
+            match me.buffer.poll_recv(cx) {
+                Poll::Ready(Some(item)) => {
+                    let weight = (me.core.count_fn)(&item);
+                    me.core.add(item, weight);
+                    if me.core.is_full() {
+                        // coalesce what has been pulled ahead already, not what the task keeps
+                        // pulling meanwhile, which would never let an always ready upstream go
+                        for _ in 0..me.buffer.len() {
+                            let Poll::Ready(Some(item)) = me.buffer.poll_recv(cx) else {
+                                break;
+                            };
+                            let weight = (me.core.count_fn)(&item);
+                            me.core.add(item, weight);
+                        }
+                        return Poll::Ready(Some(me.core.flush().0));
+                    }
+                }
+                Poll::Ready(None) => {
+                    *me.done = true;
+                    return Poll::Ready(me.core.finish().map(|(items, _)| items));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::Stream;
+use pin_project_lite::pin_project;
+
+use crate::min_batch::MinBatch;
+
+pin_project! {
+    /// Batches like [`MinBatch`] but every batch is yielded together with its zero-based index.
+    /// Indices are contiguous, the terminal partial batch included.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchEnumerate<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) batches: MinBatch<S, F, T>,
+        next_index: usize,
+    }
+}
+
+impl<S, F, T> MinBatchEnumerate<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchEnumerate {
+            batches: MinBatch::new(stream, min_batch_weight, count_fn),
+            next_index: 0,
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchEnumerate<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = (usize, Vec<S::Item>);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.project();
+        let next_index = me.next_index;
+        me.batches.poll_next(cx).map(|batch| {
+            batch.map(|items| {
+                let index = *next_index;
+                *next_index += 1;
+                (index, items)
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.batches.size_hint()
+    }
+}
@@ -0,0 +1,77 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but with "flush before adding"
+    /// semantics: when the incoming item would make the batch reach `min_batch_weight`, the
+    /// batch is yielded without it and the item starts the next one. Batches therefore stay
+    /// under `min_batch_weight`, except for a single item meeting the threshold on its own,
+    /// which is yielded alone.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchExclusive<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        pub(crate) held: Option<(S::Item, usize)>,
+    }
+}
+
+impl<S, F, T> MinBatchExclusive<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchExclusive {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            held: None,
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchExclusive<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            let (item, weight) = match me.held.take() {
+                Some(held) => held,
+                None => match ready!(me.stream.as_mut().poll_next(cx)) {
+                    Some(item) => {
+                        let weight = (me.core.count_fn)(&item);
+                        (item, weight)
+                    }
+                    None => return Poll::Ready(me.core.finish().map(|(items, _)| items)),
+                },
+            };
+            if !me.core.items.is_empty()
+                && me.core.current_batch_weight.saturating_add(weight) >= me.core.min_batch_weight
+            {
+                *me.held = Some((item, weight));
+                return Poll::Ready(Some(me.core.flush().0));
+            }
+            if let Some((items, _)) = me.core.push(item, weight) {
+                return Poll::Ready(Some(items));
+            }
+        }
+    }
+}
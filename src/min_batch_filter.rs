@@ -0,0 +1,66 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Filters and batches in one pass: batches like [`MinBatch`](crate::min_batch::MinBatch),
+    /// except that an item for which `count_fn` returns `None` is dropped, it is neither pushed
+    /// to the batch nor does it add to its weight. It is equivalent to
+    /// `.filter(..).min_batch(..)` with the item weighed only once.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchFilter<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> Option<usize>,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+    }
+}
+
+impl<S, F, T> MinBatchFilter<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> Option<usize>,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchFilter {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchFilter<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> Option<usize>,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let Some(weight) = (me.core.count_fn)(&item) else {
+                        continue;
+                    };
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        return Poll::Ready(Some(items));
+                    }
+                }
+                None => return Poll::Ready(me.core.finish().map(|(items, _)| items)),
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but instead of collecting the items,
+    /// each of them is folded into an accumulator as soon as it arrives. Every batch starts from a
+    /// fresh clone of `init` and yields the accumulated value.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchFold<S, F, T, A, G> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    A: Clone,
+    G: Fn(A, T) -> A,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        current_batch_weight: usize,
+        pub(crate) acc: Option<A>,
+        min_batch_weight: usize,
+        count_fn: F,
+        init: A,
+        fold_fn: G,
+    }
+}
+
+impl<S, F, T, A, G> MinBatchFold<S, F, T, A, G>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    A: Clone,
+    G: Fn(A, T) -> A,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, init: A, fold_fn: G) -> Self {
+        MinBatchFold {
+            stream: stream.fuse(),
+            current_batch_weight: 0,
+            acc: None,
+            min_batch_weight,
+            count_fn,
+            init,
+            fold_fn,
+        }
+    }
+}
+
+impl<S, F, T, A, G> Stream for MinBatchFold<S, F, T, A, G>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    A: Clone,
+    G: Fn(A, T) -> A,
+{
+    type Item = A;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let new_count = (me.count_fn)(&item);
+                    let acc = me.acc.take().unwrap_or_else(|| me.init.clone());
+                    *me.acc = Some((me.fold_fn)(acc, item));
+                    *me.current_batch_weight = me.current_batch_weight.saturating_add(new_count);
+                    if me.current_batch_weight >= me.min_batch_weight {
+                        *me.current_batch_weight = 0;
+                        return Poll::Ready(me.acc.take());
+                    }
+                }
+                None => {
+                    *me.current_batch_weight = 0;
+                    return Poll::Ready(me.acc.take());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use std::thread;
+
+/// The number of CPUs batches are spread over, [`thread::available_parallelism`], or `1` when it
+/// can't be determined, e.g. on platforms that don't report it or in a sandbox hiding it.
+pub fn available_cpus() -> usize {
+    thread::available_parallelism().map_or(1, |cpus| cpus.get())
+}
+
+/// The usual tuning heuristic for feeding parallel workers: a `min_batch_weight` that makes the
+/// roughly `total_weight_hint` expected from the stream fill one batch per CPU. A `cpus` of `0`
+/// behaves like `1`. A hint lighter than the number of CPUs gives `0`, that is a batch per item.
+pub fn weight_per_cpu(total_weight_hint: usize, cpus: usize) -> usize {
+    total_weight_hint / cpus.max(1)
+}
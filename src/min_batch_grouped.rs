@@ -0,0 +1,90 @@
+use alloc::vec::Vec;
+use core::hash::Hash;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+use std::collections::HashMap;
+
+use crate::batch_sink::BatchSink;
+use crate::batcher::Batcher;
+
+/// The values of a batch grouped by key, as a [`BatchSink`] of key-value pairs.
+#[derive(Debug)]
+pub(crate) struct Groups<K, V> {
+    groups: HashMap<K, Vec<V>>,
+    len: usize,
+}
+
+impl<K, V> Default for Groups<K, V> {
+    fn default() -> Self {
+        Groups {
+            groups: HashMap::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<K, V> BatchSink<(K, V)> for Groups<K, V>
+where
+    K: Hash + Eq,
+{
+    fn push(&mut self, (key, value): (K, V)) {
+        self.groups.entry(key).or_default().push(value);
+        self.len += 1;
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+pin_project! {
+    /// Batches a stream of key-value pairs like [`MinBatch`](crate::min_batch::MinBatch) but
+    /// yields every batch as a `HashMap` grouping the values by key, in their arrival order.
+    /// `count_fn` weighs whole pairs, so a batch is flushed once the total weight of its pairs
+    /// meets `min_batch_weight`, no matter how many distinct keys they have.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchGrouped<S, F, K, V> where
+    S: Stream<Item = (K, V)>,
+    F: FnMut(&(K, V)) -> usize,
+    K: Hash,
+    K: Eq,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Groups<K, V>, usize>,
+    }
+}
+
+impl<S, F, K, V> MinBatchGrouped<S, F, K, V>
+where
+    S: Stream<Item = (K, V)>,
+    F: FnMut(&(K, V)) -> usize,
+    K: Hash + Eq,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchGrouped {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn),
+        }
+    }
+}
+
+impl<S, F, K, V> Stream for MinBatchGrouped<S, F, K, V>
+where
+    S: Stream<Item = (K, V)>,
+    F: FnMut(&(K, V)) -> usize,
+    K: Hash + Eq,
+{
+    type Item = HashMap<K, Vec<V>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.project();
+        me.core
+            .poll_next(me.stream, cx)
+            .map(|batch| batch.map(|(items, _)| items.groups))
+    }
+}
@@ -0,0 +1,62 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but `count_fn` also receives the
+    /// zero-based index the item is going to have within the current batch.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchIndexed<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T, usize) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+    }
+}
+
+impl<S, F, T> MinBatchIndexed<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T, usize) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchIndexed {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchIndexed<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T, usize) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let weight = (me.core.count_fn)(&item, me.core.buffered);
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        return Poll::Ready(Some(items));
+                    }
+                }
+                None => return Poll::Ready(me.core.finish().map(|(items, _)| items)),
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::Stream;
+use pin_project_lite::pin_project;
+
+use crate::min_batch_with_weight::MinBatchWithWeight;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) and lets `inspect` look at every
+    /// batch and its weight right before it is yielded, the terminal partial one included. Unlike
+    /// [`MinBatchObserved`](crate::min_batch_observed::MinBatchObserved), `inspect` sees the
+    /// items themselves, as a shared slice so that the yielded batch is left unchanged.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchInspect<S, F, O, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    O: Fn(&[T], usize),
+{
+        #[pin]
+        pub(crate) batches: MinBatchWithWeight<S, F, T, usize>,
+        inspect: O,
+    }
+}
+
+impl<S, F, O, T> MinBatchInspect<S, F, O, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    O: Fn(&[T], usize),
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, inspect: O) -> Self {
+        MinBatchInspect {
+            batches: MinBatchWithWeight::new(stream, min_batch_weight, count_fn),
+            inspect,
+        }
+    }
+}
+
+impl<S, F, O, T> Stream for MinBatchInspect<S, F, O, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    O: Fn(&[T], usize),
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.project();
+        let inspect = me.inspect;
+        me.batches.poll_next(cx).map(|batch| {
+            batch.map(|(items, weight)| {
+                inspect(&items, weight);
+                items
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.batches.size_hint()
+    }
+}
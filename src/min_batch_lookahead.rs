@@ -0,0 +1,79 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Peekable, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but peeks at the next item before
+    /// pushing it. `fit_fn(current_weight, next_weight)` decides whether the next item still
+    /// belongs to a non-empty batch in progress, if not, the batch is flushed and the item starts
+    /// the next one.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchLookahead<S, F, P, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(usize, usize) -> bool,
+{
+        #[pin]
+        pub(crate) stream: Peekable<S>,
+        next_weight: Option<usize>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        fit_fn: P,
+    }
+}
+
+impl<S, F, P, T> MinBatchLookahead<S, F, P, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(usize, usize) -> bool,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, fit_fn: P) -> Self {
+        MinBatchLookahead {
+            stream: stream.peekable(),
+            next_weight: None,
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            fit_fn,
+        }
+    }
+}
+
+impl<S, F, P, T> Stream for MinBatchLookahead<S, F, P, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(usize, usize) -> bool,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            let next_weight = match ready!(me.stream.as_mut().poll_peek(cx)) {
+                Some(item) => *me
+                    .next_weight
+                    .get_or_insert_with(|| (me.core.count_fn)(item)),
+                None => return Poll::Ready(me.core.finish().map(|(items, _)| items)),
+            };
+            if !me.core.items.is_empty() && !(me.fit_fn)(me.core.current_batch_weight, next_weight)
+            {
+                return Poll::Ready(Some(me.core.flush().0));
+            }
+            // the item is already peeked, so it is taken right away
+            if let Some(item) = ready!(me.stream.as_mut().poll_next(cx)) {
+                *me.next_weight = None;
+                if let Some((items, _)) = me.core.push(item, next_weight) {
+                    return Poll::Ready(Some(items));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use alloc::vec::Vec;
+use core::num::NonZeroUsize;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but `count_fn` returns a
+    /// `NonZeroUsize`, so every item makes progress towards `min_batch_weight` by construction and
+    /// a batch never holds more than `min_batch_weight` items. Prefer it over weighing by byte
+    /// length or element count whenever an item could otherwise weigh `0`, which makes a plain
+    /// [`MinBatch`](crate::min_batch::MinBatch) buffer without bound.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchNonZero<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> NonZeroUsize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+    }
+}
+
+impl<S, F, T> MinBatchNonZero<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> NonZeroUsize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchNonZero {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchNonZero<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> NonZeroUsize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let weight = (me.core.count_fn)(&item).get();
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        return Poll::Ready(Some(items));
+                    }
+                }
+                None => return Poll::Ready(me.core.finish().map(|(items, _)| items)),
+            }
+        }
+    }
+}
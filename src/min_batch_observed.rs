@@ -0,0 +1,64 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::Stream;
+use pin_project_lite::pin_project;
+
+use crate::min_batch_with_weight::MinBatchWithWeight;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) and calls `on_flush` with the number
+    /// of items and the weight of every yielded batch, the terminal partial one included. The
+    /// yielded batches are left untouched, which keeps it cheaper than
+    /// [`MinBatchWithStats`](crate::min_batch_with_stats::MinBatchWithStats) when only counters
+    /// need to be updated.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchObserved<S, F, O, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    O: Fn(usize, usize),
+{
+        #[pin]
+        pub(crate) batches: MinBatchWithWeight<S, F, T, usize>,
+        on_flush: O,
+    }
+}
+
+impl<S, F, O, T> MinBatchObserved<S, F, O, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    O: Fn(usize, usize),
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, on_flush: O) -> Self {
+        MinBatchObserved {
+            batches: MinBatchWithWeight::new(stream, min_batch_weight, count_fn),
+            on_flush,
+        }
+    }
+}
+
+impl<S, F, O, T> Stream for MinBatchObserved<S, F, O, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    O: Fn(usize, usize),
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.project();
+        let on_flush = me.on_flush;
+        me.batches.poll_next(cx).map(|batch| {
+            batch.map(|(items, weight)| {
+                on_flush(items.len(), weight);
+                items
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.batches.size_hint()
+    }
+}
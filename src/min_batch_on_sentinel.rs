@@ -0,0 +1,76 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but an item for which `is_sentinel`
+    /// returns `true`, e.g. an explicit flush marker of the protocol, yields the batch in
+    /// progress right away, regardless of its weight. Unlike the barriers of
+    /// [`MinBatchWithBarriers`](crate::min_batch_with_barriers::MinBatchWithBarriers), the
+    /// sentinel itself is dropped, it is never weighed nor yielded. A sentinel arriving while
+    /// nothing is buffered yields nothing.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchOnSentinel<S, F, P, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(&T) -> bool,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        is_sentinel: P,
+    }
+}
+
+impl<S, F, P, T> MinBatchOnSentinel<S, F, P, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(&T) -> bool,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, is_sentinel: P) -> Self {
+        MinBatchOnSentinel {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            is_sentinel,
+        }
+    }
+}
+
+impl<S, F, P, T> Stream for MinBatchOnSentinel<S, F, P, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(&T) -> bool,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) if (me.is_sentinel)(&item) => {
+                    if let Some((items, _)) = me.core.take_pending() {
+                        return Poll::Ready(Some(items));
+                    }
+                }
+                Some(item) => {
+                    let weight = (me.core.count_fn)(&item);
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        return Poll::Ready(Some(items));
+                    }
+                }
+                None => return Poll::Ready(me.core.finish().map(|(items, _)| items)),
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, FuturesUnordered, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::min_batch::MinBatch;
+
+pin_project! {
+    /// Tags a future with the sequence number of its batch.
+    pub(crate) struct Sequenced<Fut> {
+        #[pin]
+        fut: Fut,
+        seq: usize,
+    }
+}
+
+impl<Fut: Future> Future for Sequenced<Fut> {
+    type Output = (usize, Fut::Output);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.project();
+        let output = ready!(me.fut.poll(cx));
+        Poll::Ready((*me.seq, output))
+    }
+}
+
+pin_project! {
+    /// Batches like [`MinBatch`] and runs `f` on every batch, with up to `concurrency` of the
+    /// returned futures in flight at once, yet yields their outputs in the order of the batches,
+    /// like `buffered` does. Outputs completed ahead of their turn wait in a reorder buffer.
+    ///
+    /// The futures are polled by the task polling this stream, spawn inside `f` to run them on
+    /// other threads. Beware of head-of-line blocking: a slow batch holds back the outputs of all
+    /// the following ones, and since the buffered outputs count against `concurrency`, no new
+    /// batch is started until it completes. A `concurrency` of `0` behaves like `1`.
+    #[must_use = "streams do nothing unless polled"]
+    pub struct MinBatchOrderedParallel<S, F, G, Fut, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    G: Fn(Vec<T>) -> Fut,
+    Fut: Future,
+{
+        #[pin]
+        pub(crate) batches: Fuse<MinBatch<S, F, T>>,
+        pub(crate) in_flight: FuturesUnordered<Sequenced<Fut>>,
+        pub(crate) reorder: BTreeMap<usize, Fut::Output>,
+        next_seq: usize,
+        next_out: usize,
+        concurrency: usize,
+        f: G,
+    }
+}
+
+impl<S, F, G, Fut, T> MinBatchOrderedParallel<S, F, G, Fut, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    G: Fn(Vec<T>) -> Fut,
+    Fut: Future,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, concurrency: usize, f: G) -> Self {
+        MinBatchOrderedParallel {
+            batches: MinBatch::new(stream, min_batch_weight, count_fn).fuse(),
+            in_flight: FuturesUnordered::new(),
+            reorder: BTreeMap::new(),
+            next_seq: 0,
+            next_out: 0,
+            concurrency: concurrency.max(1),
+            f,
+        }
+    }
+}
+
+impl<S, F, G, Fut, T> Stream for MinBatchOrderedParallel<S, F, G, Fut, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    G: Fn(Vec<T>) -> Fut,
+    Fut: Future,
+{
+    type Item = Fut::Output;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            while me.in_flight.len() + me.reorder.len() < *me.concurrency {
+                match me.batches.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(batch)) => {
+                        me.in_flight.push(Sequenced {
+                            fut: (me.f)(batch),
+                            seq: *me.next_seq,
+                        });
+                        *me.next_seq += 1;
+                    }
+                    Poll::Ready(None) | Poll::Pending => break,
+                }
+            }
+            if let Some(output) = me.reorder.remove(me.next_out) {
+                *me.next_out += 1;
+                return Poll::Ready(Some(output));
+            }
+            match me.in_flight.poll_next_unpin(cx) {
+                Poll::Ready(Some((seq, output))) => {
+                    me.reorder.insert(seq, output);
+                }
+                Poll::Ready(None) if me.batches.is_done() => return Poll::Ready(None),
+                Poll::Ready(None) | Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+use std::sync::Mutex;
+
+use crate::batcher::Batcher;
+
+/// A shared free list of batch buffers. Buffers handed out by [`BatchPool::get`] come back
+/// through [`BatchPool::recycle`], typically when a [`PooledBatch`] is dropped, and keep their
+/// capacity, so that once warmed up batching allocates nothing.
+#[derive(Debug)]
+pub struct BatchPool<T> {
+    free: Arc<Mutex<Vec<Vec<T>>>>,
+    capacity: usize,
+}
+
+impl<T> BatchPool<T> {
+    /// Creates an empty pool, new buffers are allocated with `capacity`.
+    pub fn new(capacity: usize) -> Self {
+        BatchPool {
+            free: Arc::new(Mutex::new(Vec::new())),
+            capacity,
+        }
+    }
+
+    /// Takes a free buffer out of the pool, or allocates a new one if there is none.
+    pub fn get(&self) -> Vec<T> {
+        self.free
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .pop()
+            .unwrap_or_else(|| Vec::with_capacity(self.capacity))
+    }
+
+    /// Clears `buffer` and returns it to the pool.
+    pub fn recycle(&self, mut buffer: Vec<T>) {
+        buffer.clear();
+        self.free
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(buffer);
+    }
+
+    /// Number of free buffers in the pool.
+    pub fn free_len(&self) -> usize {
+        self.free
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .len()
+    }
+}
+
+/// Shares the same free list.
+impl<T> Clone for BatchPool<T> {
+    fn clone(&self) -> Self {
+        BatchPool {
+            free: Arc::clone(&self.free),
+            capacity: self.capacity,
+        }
+    }
+}
+
+/// A batch whose buffer returns to its [`BatchPool`] when dropped. Dereferences to the `Vec` of
+/// items, use [`PooledBatch::into_inner`] to keep the buffer out of the pool instead.
+#[derive(Debug)]
+pub struct PooledBatch<T> {
+    items: Vec<T>,
+    pool: BatchPool<T>,
+}
+
+impl<T> PooledBatch<T> {
+    /// Returns the buffer to the pool right away, same as dropping the batch.
+    pub fn recycle(self) {}
+
+    /// Detaches the buffer from the pool.
+    pub fn into_inner(mut self) -> Vec<T> {
+        core::mem::take(&mut self.items)
+    }
+}
+
+impl<T> Deref for PooledBatch<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Vec<T> {
+        &self.items
+    }
+}
+
+impl<T> DerefMut for PooledBatch<T> {
+    fn deref_mut(&mut self) -> &mut Vec<T> {
+        &mut self.items
+    }
+}
+
+impl<T> Drop for PooledBatch<T> {
+    fn drop(&mut self) {
+        // a detached buffer leaves an unallocated `Vec` behind, not worth pooling
+        if self.items.capacity() > 0 {
+            self.pool.recycle(core::mem::take(&mut self.items));
+        }
+    }
+}
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but accumulates into buffers taken
+    /// from `pool` and yields them as [`PooledBatch`], which hands the buffer back to the pool once
+    /// the consumer is done with it.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchPooled<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        pool: BatchPool<S::Item>,
+    }
+}
+
+impl<S, F, T> MinBatchPooled<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, pool: BatchPool<T>) -> Self {
+        MinBatchPooled {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn),
+            pool,
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchPooled<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = PooledBatch<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    if me.core.items.is_empty() {
+                        me.core.items = me.pool.get();
+                    }
+                    let weight = (me.core.count_fn)(&item);
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        return Poll::Ready(Some(PooledBatch {
+                            items,
+                            pool: me.pool.clone(),
+                        }));
+                    }
+                }
+                None => {
+                    let last = me.core.finish().map(|(items, _)| PooledBatch {
+                        items,
+                        pool: me.pool.clone(),
+                    });
+                    return Poll::Ready(last);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches a stream of `(item, weight)` pairs whose producer already knows the weights, the
+    /// weights are stripped from the yielded batches and no `count_fn` is needed. It is the
+    /// inverse of [`MinBatchTagged`](crate::min_batch_tagged::MinBatchTagged), which weighs
+    /// plain items and yields them tagged. To keep the weights in the batches, use
+    /// [`min_batch_prekeyed_tagged`](crate::ext::MinBatchExt::min_batch_prekeyed_tagged).
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchPrekeyed<S, T> where
+    S: Stream<Item = (T, usize)>,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<(), Vec<T>, usize>,
+    }
+}
+
+impl<S, T> MinBatchPrekeyed<S, T>
+where
+    S: Stream<Item = (T, usize)>,
+{
+    pub fn new(stream: S, min_batch_weight: usize) -> Self {
+        MinBatchPrekeyed {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, ()).reserving(initial_capacity(min_batch_weight)),
+        }
+    }
+}
+
+impl<S, T> Stream for MinBatchPrekeyed<S, T>
+where
+    S: Stream<Item = (T, usize)>,
+{
+    type Item = Vec<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some((item, weight)) => {
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        return Poll::Ready(Some(items));
+                    }
+                }
+                None => return Poll::Ready(me.core.finish().map(|(items, _)| items)),
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::Stream;
+use pin_project_lite::pin_project;
+
+use crate::min_batch::MinBatch;
+
+pin_project! {
+    /// Batches like [`MinBatch`] but every batch is yielded together with the half-open range of the
+    /// zero-based stream positions of its items, e.g. `0..2` then `2..5`, so that downstream can
+    /// correlate batches back to the source. Ranges are contiguous, the terminal partial batch
+    /// included, and the length of a range is the length of its batch.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchRanged<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) batches: MinBatch<S, F, T>,
+        next_position: usize,
+    }
+}
+
+impl<S, F, T> MinBatchRanged<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchRanged {
+            batches: MinBatch::new(stream, min_batch_weight, count_fn),
+            next_position: 0,
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchRanged<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = (Vec<S::Item>, Range<usize>);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.project();
+        let next_position = me.next_position;
+        me.batches.poll_next(cx).map(|batch| {
+            batch.map(|items| {
+                let start = *next_position;
+                *next_position += items.len();
+                (items, start..*next_position)
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.batches.size_hint()
+    }
+}
@@ -0,0 +1,97 @@
+use alloc::vec::Vec;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+use std::time::Duration;
+use tokio::time::{sleep, Instant, Sleep};
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but yields batches no more often
+    /// than once per `min_interval`. A batch that meets `min_batch_weight` before the interval
+    /// since the previous batch has elapsed keeps accumulating incoming items until it does, so
+    /// under load batches get fuller instead of more frequent. The first batch is not delayed,
+    /// the final partial one respects the interval too. While a batch waits, at most one item is
+    /// pulled per poll, so an always ready upstream neither starves the executor nor the timer.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchRateLimited<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        #[pin]
+        delay: Option<Sleep>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        min_interval: Duration,
+    }
+}
+
+impl<S, F, T> MinBatchRateLimited<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, min_interval: Duration) -> Self {
+        MinBatchRateLimited {
+            stream: stream.fuse(),
+            delay: None,
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            min_interval,
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchRateLimited<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            let batch_ready = !me.core.items.is_empty()
+                && (me.core.current_batch_weight >= me.core.min_batch_weight
+                    || me.stream.is_done());
+            if batch_ready {
+                let delayed = match me.delay.as_mut().as_pin_mut() {
+                    // an always ready stream keeps the timer driver from running, so the clock is
+                    // checked too
+                    Some(delay) => delay.deadline() > Instant::now() && delay.poll(cx).is_pending(),
+                    None => false,
+                };
+                if !delayed {
+                    me.delay.set(Some(sleep(*me.min_interval)));
+                    return Poll::Ready(Some(me.core.flush().0));
+                }
+                if me.stream.is_done() {
+                    return Poll::Pending;
+                }
+                // while delayed, control goes back to the executor after every pulled item
+                if let Some(item) = ready!(me.stream.as_mut().poll_next(cx)) {
+                    let weight = (me.core.count_fn)(&item);
+                    me.core.add(item, weight);
+                    cx.waker().wake_by_ref();
+                    return Poll::Pending;
+                }
+                continue;
+            } else if me.stream.is_done() {
+                return Poll::Ready(None);
+            }
+            if let Some(item) = ready!(me.stream.as_mut().poll_next(cx)) {
+                let weight = (me.core.count_fn)(&item);
+                me.core.add(item, weight);
+            }
+        }
+    }
+}
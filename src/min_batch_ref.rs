@@ -0,0 +1,69 @@
+use alloc::vec::Vec;
+use core::ops::Deref;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches a stream of references, or of any other pointer dereferencing to `T`, like
+    /// [`MinBatch`](crate::min_batch::MinBatch) but `count_fn` takes the pointee `&T` rather
+    /// than `&&T`. The items are moved into the batches as they are: a stream of `&'a T` yields
+    /// `Vec<&'a T>` borrowing from the same data as the source, nothing is cloned, and the
+    /// batches cannot outlive it.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchRef<S, F, T: ?Sized> where
+    S: Stream,
+    S::Item: Deref<Target = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<S::Item>, usize>,
+    }
+}
+
+impl<S, F, T: ?Sized> MinBatchRef<S, F, T>
+where
+    S: Stream,
+    S::Item: Deref<Target = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchRef {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+        }
+    }
+}
+
+impl<S, F, T: ?Sized> Stream for MinBatchRef<S, F, T>
+where
+    S: Stream,
+    S::Item: Deref<Target = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let weight = (me.core.count_fn)(&*item);
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        return Poll::Ready(Some(items));
+                    }
+                }
+                None => return Poll::Ready(me.core.finish().map(|(items, _)| items)),
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::Stream;
+use pin_project_lite::pin_project;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::min_batch_with_weight::MinBatchWithWeight;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) and yields plain batches, while the
+    /// total weight of every batch, the terminal partial one included, is sent to `weights` as a
+    /// side effect, e.g. for metrics.
+    ///
+    /// Sending never blocks the stream. Once the receiver is dropped the weights are discarded and
+    /// batching goes on as before.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchReporting<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) batches: MinBatchWithWeight<S, F, T, usize>,
+        weights: UnboundedSender<usize>,
+    }
+}
+
+impl<S, F, T> MinBatchReporting<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(
+        stream: S,
+        min_batch_weight: usize,
+        count_fn: F,
+        weights: UnboundedSender<usize>,
+    ) -> Self {
+        MinBatchReporting {
+            batches: MinBatchWithWeight::new(stream, min_batch_weight, count_fn),
+            weights,
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchReporting<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.project();
+        let weights = me.weights;
+        me.batches.poll_next(cx).map(|batch| {
+            batch.map(|(items, weight)| {
+                let _ = weights.send(weight);
+                items
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.batches.size_hint()
+    }
+}
@@ -0,0 +1,109 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+/// What [`MinBatchResults`] does after yielding an `Err`. Either way, the batch in progress is
+/// yielded as `Ok` before the `Err`, so no buffered value is lost.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// Terminate the stream after the first `Err`.
+    #[default]
+    Abort,
+    /// Keep batching the `Ok` values following an `Err`, e.g. to get around transient errors.
+    Continue,
+}
+
+pin_project! {
+    /// Batches the `Ok` values of a stream of `Result`s, `count_fn` only sees the `Ok` values.
+    ///
+    /// An `Err` yields the batch in progress as `Ok` first, then the `Err`. With
+    /// [`ErrorPolicy::Abort`], the default, the stream terminates afterwards, with
+    /// [`ErrorPolicy::Continue`] it goes on batching the following `Ok` values.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchResults<S, F, T, E> where
+    S: Stream<Item = Result<T, E>>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        pub(crate) error: Option<E>,
+        pub(crate) done: bool,
+        policy: ErrorPolicy,
+    }
+}
+
+impl<S, F, T, E> MinBatchResults<S, F, T, E>
+where
+    S: Stream<Item = Result<T, E>>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchResults::new_with_policy(stream, min_batch_weight, count_fn, ErrorPolicy::Abort)
+    }
+
+    /// Like [`MinBatchResults::new`] but `policy` decides whether an `Err` terminates the stream.
+    pub fn new_with_policy(
+        stream: S,
+        min_batch_weight: usize,
+        count_fn: F,
+        policy: ErrorPolicy,
+    ) -> Self {
+        MinBatchResults {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            error: None,
+            done: false,
+            policy,
+        }
+    }
+}
+
+impl<S, F, T, E> Stream for MinBatchResults<S, F, T, E>
+where
+    S: Stream<Item = Result<T, E>>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Result<Vec<T>, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        if *me.done {
+            return Poll::Ready(None);
+        }
+        if let Some(error) = me.error.take() {
+            *me.done = *me.policy == ErrorPolicy::Abort;
+            return Poll::Ready(Some(Err(error)));
+        }
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(Ok(item)) => {
+                    let weight = (me.core.count_fn)(&item);
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        return Poll::Ready(Some(Ok(items)));
+                    }
+                }
+                Some(Err(error)) => match me.core.take_pending() {
+                    Some((items, _)) => {
+                        *me.error = Some(error);
+                        return Poll::Ready(Some(Ok(items)));
+                    }
+                    None => {
+                        *me.done = *me.policy == ErrorPolicy::Abort;
+                        return Poll::Ready(Some(Err(error)));
+                    }
+                },
+                None => return Poll::Ready(me.core.finish().map(|(items, _)| Ok(items))),
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use alloc::sync::Arc;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::Stream;
+use pin_project_lite::pin_project;
+
+use crate::min_batch::MinBatch;
+
+pin_project! {
+    /// Batches like [`MinBatch`] but yields every batch as an `Arc<[T]>`, which is cheap to clone
+    /// when a batch is fanned out to several consumers. Converting a batch moves its items into the
+    /// shared allocation.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchShared<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) batches: MinBatch<S, F, T>,
+    }
+}
+
+impl<S, F, T> MinBatchShared<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchShared {
+            batches: MinBatch::new(stream, min_batch_weight, count_fn),
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchShared<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Arc<[S::Item]>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project()
+            .batches
+            .poll_next(cx)
+            .map(|batch| batch.map(Arc::from))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.batches.size_hint()
+    }
+}
@@ -0,0 +1,102 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but yields overlapping windows: after
+    /// a batch is yielded, its trailing items are retained as the start of the next batch, the
+    /// fewest that weigh at least `overlap_weight` together, though never the whole batch. The
+    /// retained items count towards the weight of the next batch too, which always takes at
+    /// least one new item, so a window never repeats the previous one. An `overlap_weight` of
+    /// `0` gives the disjoint batches of `MinBatch`.
+    ///
+    /// The yielded batch owns its items, the retained ones are therefore cloned, hence
+    /// `T: Clone`. The stream ends without a terminal batch if no item arrived after the last
+    /// window.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchSliding<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    T: Clone,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        // only the items pushed since the last window count as buffered in `core`
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        weights: Vec<usize>,
+        overlap_weight: usize,
+    }
+}
+
+impl<S, F, T> MinBatchSliding<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    T: Clone,
+{
+    pub fn new(stream: S, min_batch_weight: usize, overlap_weight: usize, count_fn: F) -> Self {
+        MinBatchSliding {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            weights: Vec::with_capacity(initial_capacity(min_batch_weight)),
+            overlap_weight,
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchSliding<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    T: Clone,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let weight = (me.core.count_fn)(&item);
+                    me.weights.push(weight);
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        // the shortest tail reaching the overlap, short of the whole batch
+                        let mut tail_len = 0;
+                        let mut tail_weight = 0usize;
+                        while tail_weight < *me.overlap_weight && tail_len + 1 < items.len() {
+                            tail_len += 1;
+                            tail_weight =
+                                tail_weight.saturating_add(me.weights[me.weights.len() - tail_len]);
+                        }
+                        let tail_start = items.len() - tail_len;
+                        me.weights.drain(..tail_start);
+                        for (item, weight) in items[tail_start..].iter().zip(me.weights.iter()) {
+                            me.core.retain(item.clone(), *weight);
+                        }
+                        return Poll::Ready(Some(items));
+                    }
+                }
+                None => {
+                    me.weights.clear();
+                    // the retained overlap alone was yielded already
+                    let last = if me.core.buffered == 0 {
+                        me.core.reset();
+                        None
+                    } else {
+                        me.core.finish().map(|(items, _)| items)
+                    };
+                    return Poll::Ready(last);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::Stream;
+use pin_project_lite::pin_project;
+
+use crate::min_batch::MinBatch;
+
+pin_project! {
+    /// Batches like [`MinBatch`] and sorts every batch by `key_fn`, the terminal partial one
+    /// included, before yielding it, e.g. for merge processing downstream. Batches are cut by weight
+    /// in arrival order, sorting only reorders the items within a batch, stably like
+    /// [`slice::sort_by_key`].
+    ///
+    /// Sorting costs `O(n log n)` per batch of `n` items, paid in `poll_next` on the task
+    /// polling the stream.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchSortedByKey<S, F, KF, T, K> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    KF: Fn(&T) -> K,
+    K: Ord,
+{
+        #[pin]
+        pub(crate) batches: MinBatch<S, F, T>,
+        key_fn: KF,
+    }
+}
+
+impl<S, F, KF, T, K> MinBatchSortedByKey<S, F, KF, T, K>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    KF: Fn(&T) -> K,
+    K: Ord,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, key_fn: KF) -> Self {
+        MinBatchSortedByKey {
+            batches: MinBatch::new(stream, min_batch_weight, count_fn),
+            key_fn,
+        }
+    }
+}
+
+impl<S, F, KF, T, K> Stream for MinBatchSortedByKey<S, F, KF, T, K>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    KF: Fn(&T) -> K,
+    K: Ord,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.project();
+        let key_fn = me.key_fn;
+        me.batches.poll_next(cx).map(|batch| {
+            batch.map(|mut items| {
+                items.sort_by_key(key_fn);
+                items
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.batches.size_hint()
+    }
+}
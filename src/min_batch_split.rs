@@ -0,0 +1,89 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but an item whose weight alone
+    /// exceeds `min_batch_weight` is handed to `split_fn` and the resulting pieces are batched in
+    /// its place, before the next item of the inner stream.
+    ///
+    /// Pieces are weighed again and split again if they still exceed `min_batch_weight`, so
+    /// `split_fn` must eventually produce pieces weighing at most `min_batch_weight`, otherwise
+    /// the adapter splits forever. In particular, with a `min_batch_weight` of `0` the pieces
+    /// have to weigh `0`.
+    ///
+    /// An item that `split_fn` splits into no pieces at all is dropped, it is neither batched nor
+    /// yielded in any other way.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchSplit<S, F, P, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(T) -> Vec<T>,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        pub(crate) pieces: VecDeque<S::Item>,
+        split_fn: P,
+    }
+}
+
+impl<S, F, P, T> MinBatchSplit<S, F, P, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(T) -> Vec<T>,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, split_fn: P) -> Self {
+        MinBatchSplit {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            pieces: VecDeque::new(),
+            split_fn,
+        }
+    }
+}
+
+impl<S, F, P, T> Stream for MinBatchSplit<S, F, P, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(T) -> Vec<T>,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            let next = match me.pieces.pop_front() {
+                Some(piece) => Some(piece),
+                None => ready!(me.stream.as_mut().poll_next(cx)),
+            };
+            match next {
+                Some(item) => {
+                    let weight = (me.core.count_fn)(&item);
+                    if weight > me.core.min_batch_weight {
+                        for piece in (me.split_fn)(item).into_iter().rev() {
+                            me.pieces.push_front(piece);
+                        }
+                        continue;
+                    }
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        return Poll::Ready(Some(items));
+                    }
+                }
+                None => return Poll::Ready(me.core.finish().map(|(items, _)| items)),
+            }
+        }
+    }
+}
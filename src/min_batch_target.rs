@@ -0,0 +1,58 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches items so that every batch weighs close to `target`, within `tolerance`. A batch is
+    /// yielded once it weighs at least `target - tolerance`, and it is also yielded early when
+    /// the incoming item would overshoot `target + tolerance`, in which case that item starts the
+    /// next batch. An item overshooting on its own is yielded alone.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchTarget<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+    }
+}
+
+impl<S, F, T> MinBatchTarget<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, target: usize, tolerance: usize, count_fn: F) -> Self {
+        let mut core = Batcher::new(target.saturating_sub(tolerance), count_fn)
+            .reserving(initial_capacity(target));
+        // an item that would overshoot the range starts the next batch instead
+        core.max_batch_weight = Some(target.saturating_add(tolerance));
+        MinBatchTarget {
+            stream: stream.fuse(),
+            core,
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchTarget<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.project();
+        me.core
+            .poll_next(me.stream, cx)
+            .map(|batch| batch.map(|(items, _)| items))
+    }
+}
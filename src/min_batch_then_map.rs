@@ -0,0 +1,56 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::Stream;
+use pin_project_lite::pin_project;
+
+use crate::min_batch::MinBatch;
+
+pin_project! {
+    /// Batches like [`MinBatch`] and yields every batch mapped by `map_fn`, the terminal partial one
+    /// included. It is equivalent to `.min_batch(min_batch_weight, count_fn).map(map_fn)` in a single
+    /// adapter, which keeps the type of the pipeline shorter.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchThenMap<S, F, G, T, U> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    G: Fn(Vec<T>) -> U,
+{
+        #[pin]
+        pub(crate) batches: MinBatch<S, F, T>,
+        map_fn: G,
+    }
+}
+
+impl<S, F, G, T, U> MinBatchThenMap<S, F, G, T, U>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    G: Fn(Vec<T>) -> U,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, map_fn: G) -> Self {
+        MinBatchThenMap {
+            batches: MinBatch::new(stream, min_batch_weight, count_fn),
+            map_fn,
+        }
+    }
+}
+
+impl<S, F, G, T, U> Stream for MinBatchThenMap<S, F, G, T, U>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    G: Fn(Vec<T>) -> U,
+{
+    type Item = U;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.project();
+        me.batches.poll_next(cx).map(|batch| batch.map(me.map_fn))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.batches.size_hint()
+    }
+}
@@ -0,0 +1,55 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::Stream;
+use pin_project_lite::pin_project;
+use tokio::time::Instant;
+
+use crate::min_batch::MinBatch;
+
+pin_project! {
+    /// Batches like [`MinBatch`] but every batch is yielded together with the `Instant` it was
+    /// flushed at, captured right before it is returned, so that the intervals between batches can be
+    /// computed downstream. The instants are monotonic, hence never decrease from one batch to the
+    /// next.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchTimestamped<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) batches: MinBatch<S, F, T>,
+    }
+}
+
+impl<S, F, T> MinBatchTimestamped<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchTimestamped {
+            batches: MinBatch::new(stream, min_batch_weight, count_fn),
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchTimestamped<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = (Vec<S::Item>, Instant);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project()
+            .batches
+            .poll_next(cx)
+            .map(|batch| batch.map(|items| (items, Instant::now())))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.batches.size_hint()
+    }
+}
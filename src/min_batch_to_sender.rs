@@ -0,0 +1,32 @@
+use futures::stream::Stream;
+use futures::StreamExt;
+use tokio::sync::mpsc::{error::SendError, Sender};
+
+use crate::min_batch::MinBatch;
+
+/// Drives `stream` through [`MinBatch`] and sends every batch to `sender`, including the final
+/// partial one. A full channel applies backpressure: the next batch is not accumulated until
+/// the current one has been sent.
+///
+/// Returns once the stream has ended and every batch has been sent, or fails with the batch that
+/// could not be delivered as soon as the receiver is dropped, leaving the rest of the stream
+/// unpolled.
+pub async fn min_batch_to_sender<S, F, T>(
+    stream: S,
+    min_batch_weight: usize,
+    count_fn: F,
+    sender: Sender<Vec<T>>,
+) -> Result<(), SendError<Vec<T>>>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    let mut batches = core::pin::pin!(MinBatch::new(stream, min_batch_weight, count_fn));
+    while let Some(batch) = batches.next().await {
+        match sender.reserve().await {
+            Ok(permit) => permit.send(batch),
+            Err(_) => return Err(SendError(batch)),
+        }
+    }
+    Ok(())
+}
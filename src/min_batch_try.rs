@@ -0,0 +1,78 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) with a fallible `count_fn`.
+    ///
+    /// An item whose weight cannot be computed is dropped. The batch in progress is not lost,
+    /// it is yielded as `Ok` first and the error follows as the next stream item, after which
+    /// batching continues with a fresh batch.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchTry<S, F, T, E> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> Result<usize, E>,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        pub(crate) error: Option<E>,
+    }
+}
+
+impl<S, F, T, E> MinBatchTry<S, F, T, E>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> Result<usize, E>,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchTry {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            error: None,
+        }
+    }
+}
+
+impl<S, F, T, E> Stream for MinBatchTry<S, F, T, E>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> Result<usize, E>,
+{
+    type Item = Result<Vec<S::Item>, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        if let Some(error) = me.error.take() {
+            return Poll::Ready(Some(Err(error)));
+        }
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => match (me.core.count_fn)(&item) {
+                    Ok(weight) => {
+                        if let Some((items, _)) = me.core.push(item, weight) {
+                            return Poll::Ready(Some(Ok(items)));
+                        }
+                    }
+                    Err(error) => match me.core.take_pending() {
+                        Some((items, _)) => {
+                            *me.error = Some(error);
+                            return Poll::Ready(Some(Ok(items)));
+                        }
+                        None => return Poll::Ready(Some(Err(error))),
+                    },
+                },
+                None => return Poll::Ready(me.core.finish().map(|(items, _)| Ok(items))),
+            }
+        }
+    }
+}
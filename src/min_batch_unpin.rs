@@ -0,0 +1,80 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+/// Batches like [`MinBatch`](crate::min_batch::MinBatch), specialized for `Unpin` streams.
+///
+/// The inner stream is stored as is and polled through `&mut self`, without pin projection, so
+/// the adapter is `Unpin` whenever the stream is, regardless of the closure, and the stream is
+/// freely reachable by [`get_mut`](Self::get_mut) between polls. Batches are the same as those of
+/// `MinBatch`.
+#[must_use = "streams do nothing unless polled"]
+#[derive(Debug)]
+pub struct MinBatchUnpin<S, F, T>
+where
+    S: Stream<Item = T> + Unpin,
+    F: FnMut(&T) -> usize,
+{
+    pub(crate) stream: Fuse<S>,
+    pub(crate) core: Batcher<F, Vec<T>, usize>,
+}
+
+// the adapter never pins its fields, see `poll_next`
+impl<S, F, T> Unpin for MinBatchUnpin<S, F, T>
+where
+    S: Stream<Item = T> + Unpin,
+    F: FnMut(&T) -> usize,
+{
+}
+
+impl<S, F, T> MinBatchUnpin<S, F, T>
+where
+    S: Stream<Item = T> + Unpin,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchUnpin {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+        }
+    }
+
+    /// Acquires a reference to the underlying stream.
+    pub fn get_ref(&self) -> &S {
+        self.stream.get_ref()
+    }
+
+    /// Acquires a mutable reference to the underlying stream.
+    ///
+    /// It is inadvisable to directly poll the underlying stream, as it bypasses the batching.
+    pub fn get_mut(&mut self) -> &mut S {
+        self.stream.get_mut()
+    }
+
+    /// Consumes this adapter, returning the underlying stream. The items of the batch in progress
+    /// are dropped.
+    pub fn into_inner(self) -> S {
+        self.stream.into_inner()
+    }
+}
+
+impl<S, F, T> Stream for MinBatchUnpin<S, F, T>
+where
+    S: Stream<Item = T> + Unpin,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.get_mut();
+        me.core
+            .poll_next(Pin::new(&mut me.stream), cx)
+            .map(|batch| batch.map(|(items, _)| items))
+    }
+}
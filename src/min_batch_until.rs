@@ -0,0 +1,75 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but `flush_if` is evaluated on the
+    /// buffered items after every push, and the batch is yielded as soon as it returns `true`,
+    /// e.g. once it holds both a start and an end marker. Reaching `min_batch_weight` still flushes
+    /// on its own, whichever condition holds first ends the batch. This generalizes
+    /// [`MinBatchWithBarriers`](crate::min_batch_with_barriers::MinBatchWithBarriers) to conditions
+    /// spanning several items.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchUntil<S, F, P, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(&[T]) -> bool,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        flush_if: P,
+    }
+}
+
+impl<S, F, P, T> MinBatchUntil<S, F, P, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(&[T]) -> bool,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, flush_if: P) -> Self {
+        MinBatchUntil {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            flush_if,
+        }
+    }
+}
+
+impl<S, F, P, T> Stream for MinBatchUntil<S, F, P, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(&[T]) -> bool,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let weight = (me.core.count_fn)(&item);
+                    match me.core.push(item, weight) {
+                        Some((items, _)) => return Poll::Ready(Some(items)),
+                        None if (me.flush_if)(&me.core.items) => {
+                            return Poll::Ready(Some(me.core.flush().0));
+                        }
+                        None => {}
+                    }
+                }
+                None => return Poll::Ready(me.core.finish().map(|(items, _)| items)),
+            }
+        }
+    }
+}
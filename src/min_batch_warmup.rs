@@ -0,0 +1,65 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but flushes the very first batch at
+    /// the lower `first_batch_weight`, so the pipeline downstream gets going quickly, and every
+    /// following one at `steady_batch_weight`.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchWarmup<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        steady_batch_weight: usize,
+    }
+}
+
+impl<S, F, T> MinBatchWarmup<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(
+        stream: S,
+        first_batch_weight: usize,
+        steady_batch_weight: usize,
+        count_fn: F,
+    ) -> Self {
+        MinBatchWarmup {
+            stream: stream.fuse(),
+            core: Batcher::new(first_batch_weight, count_fn)
+                .reserving(initial_capacity(first_batch_weight)),
+            steady_batch_weight,
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchWarmup<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.project();
+        let batch = ready!(me.core.poll_next(me.stream, cx));
+        if batch.is_some() {
+            me.core.min_batch_weight = *me.steady_batch_weight;
+            me.core.reserve_len = initial_capacity(*me.steady_batch_weight);
+        }
+        Poll::Ready(batch.map(|(items, _)| items))
+    }
+}
@@ -0,0 +1,70 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+
+pin_project! {
+    /// Batches items until `should_flush` says so. After every pushed item, `should_flush` receives
+    /// the buffered items and their weight accumulated with `count_fn`, and the batch is yielded
+    /// as soon as it returns `true`. This covers any combination of weight, length and content
+    /// based limits in a single hook. The predicate is never consulted for an empty batch, and
+    /// the last partial batch is yielded when the stream ends regardless of it.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchWhen<S, F, P, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(&[T], usize) -> bool,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        should_flush: P,
+    }
+}
+
+impl<S, F, P, T> MinBatchWhen<S, F, P, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(&[T], usize) -> bool,
+{
+    pub fn new(stream: S, count_fn: F, should_flush: P) -> Self {
+        MinBatchWhen {
+            stream: stream.fuse(),
+            // `should_flush` alone decides when a batch is due
+            core: Batcher::new(usize::MAX, count_fn),
+            should_flush,
+        }
+    }
+}
+
+impl<S, F, P, T> Stream for MinBatchWhen<S, F, P, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(&[T], usize) -> bool,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let weight = (me.core.count_fn)(&item);
+                    me.core.add(item, weight);
+                    if (me.should_flush)(&me.core.items, me.core.current_batch_weight) {
+                        return Poll::Ready(Some(me.core.flush().0));
+                    }
+                }
+                None => return Poll::Ready(me.core.take_pending().map(|(items, _)| items)),
+            }
+        }
+    }
+}
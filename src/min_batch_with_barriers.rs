@@ -0,0 +1,72 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but an item for which `barrier_fn`
+    /// returns `true`, e.g. an end-of-epoch marker, always ends the batch: it is pushed and the
+    /// batch is yielded right away, regardless of its weight. The barrier item still counts
+    /// towards the weight of the batch it ends.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchWithBarriers<S, F, P, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(&T) -> bool,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        barrier_fn: P,
+    }
+}
+
+impl<S, F, P, T> MinBatchWithBarriers<S, F, P, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(&T) -> bool,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, barrier_fn: P) -> Self {
+        MinBatchWithBarriers {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            barrier_fn,
+        }
+    }
+}
+
+impl<S, F, P, T> Stream for MinBatchWithBarriers<S, F, P, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    P: Fn(&T) -> bool,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let barrier = (me.barrier_fn)(&item);
+                    let weight = (me.core.count_fn)(&item);
+                    match me.core.push(item, weight) {
+                        Some((items, _)) => return Poll::Ready(Some(items)),
+                        None if barrier => return Poll::Ready(Some(me.core.flush().0)),
+                        None => {}
+                    }
+                }
+                None => return Poll::Ready(me.core.finish().map(|(items, _)| items)),
+            }
+        }
+    }
+}
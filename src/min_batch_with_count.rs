@@ -0,0 +1,55 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::Stream;
+use pin_project_lite::pin_project;
+
+use crate::min_batch::MinBatch;
+
+pin_project! {
+    /// Batches like [`MinBatch`] but every batch is yielded together with the number of items it
+    /// holds, unlike [`MinBatchWithWeight`](crate::min_batch_with_weight::MinBatchWithWeight) which
+    /// yields the weight.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchWithCount<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) batches: MinBatch<S, F, T>,
+    }
+}
+
+impl<S, F, T> MinBatchWithCount<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchWithCount {
+            batches: MinBatch::new(stream, min_batch_weight, count_fn),
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchWithCount<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = (Vec<S::Item>, usize);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().batches.poll_next(cx).map(|batch| {
+            batch.map(|items| {
+                let count = items.len();
+                (items, count)
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.batches.size_hint()
+    }
+}
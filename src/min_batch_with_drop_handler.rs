@@ -0,0 +1,76 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but hands the items still buffered
+    /// when the adapter is dropped, e.g. along with a cancelled pipeline future, to `on_drop`, so
+    /// they can be logged or persisted instead of vanishing. `on_drop` runs from `Drop`, hence it
+    /// is synchronous, and it is not called if nothing is buffered, in particular not after the
+    /// terminal batch has been yielded.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchWithDropHandler<S, F, D, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    D: FnOnce(Vec<T>),
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        on_drop: Option<D>,
+    }
+
+    impl<S, F, D, T> PinnedDrop for MinBatchWithDropHandler<S, F, D, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    D: FnOnce(Vec<T>),
+{
+        fn drop(this: Pin<&mut Self>) {
+            let me = this.project();
+            if let Some((items, _)) = me.core.take_pending() {
+                if let Some(on_drop) = me.on_drop.take() {
+                    on_drop(items);
+                }
+            }
+        }
+    }
+}
+
+impl<S, F, D, T> MinBatchWithDropHandler<S, F, D, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    D: FnOnce(Vec<T>),
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, on_drop: D) -> Self {
+        MinBatchWithDropHandler {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            on_drop: Some(on_drop),
+        }
+    }
+}
+
+impl<S, F, D, T> Stream for MinBatchWithDropHandler<S, F, D, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+    D: FnOnce(Vec<T>),
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.project();
+        me.core
+            .poll_next(me.stream, cx)
+            .map(|batch| batch.map(|(items, _)| items))
+    }
+}
@@ -0,0 +1,98 @@
+use alloc::vec::Vec;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+use std::time::Duration;
+use tokio::time::{sleep, Instant, Sleep};
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but also yields an empty batch as a
+    /// heartbeat whenever no batch has been yielded for `interval`, so downstream can tell an
+    /// idle pipeline from a stuck one. Every yielded batch, heartbeat or not, restarts the
+    /// interval, hence no heartbeat follows right after a real batch. Unlike
+    /// [`MinBatchWithTimeout`](crate::min_batch_with_timeout::MinBatchWithTimeout) a heartbeat
+    /// never flushes the buffered items, they stay pending until the threshold is met. The first
+    /// interval starts at the first poll, not when the adapter is built.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchWithHeartbeat<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        // created on the first poll, so that the adapter can be built outside of a runtime
+        #[pin]
+        heartbeat: Option<Sleep>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        interval: Duration,
+    }
+}
+
+impl<S, F, T> MinBatchWithHeartbeat<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, interval: Duration) -> Self {
+        MinBatchWithHeartbeat {
+            stream: stream.fuse(),
+            heartbeat: None,
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            interval,
+        }
+    }
+}
+
+/// Restarts the interval from now, as a batch has just been yielded.
+fn restart(heartbeat: Pin<&mut Option<Sleep>>, interval: Duration) {
+    if let Some(heartbeat) = heartbeat.as_pin_mut() {
+        heartbeat.reset(Instant::now() + interval);
+    }
+}
+
+impl<S, F, T> Stream for MinBatchWithHeartbeat<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        if me.heartbeat.is_none() {
+            me.heartbeat.set(Some(sleep(*me.interval)));
+        }
+        loop {
+            match me.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let weight = (me.core.count_fn)(&item);
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        restart(me.heartbeat.as_mut(), *me.interval);
+                        return Poll::Ready(Some(items));
+                    }
+                }
+                Poll::Ready(None) => return Poll::Ready(me.core.finish().map(|(items, _)| items)),
+                Poll::Pending => {
+                    let idle = me
+                        .heartbeat
+                        .as_mut()
+                        .as_pin_mut()
+                        .is_some_and(|heartbeat| heartbeat.poll(cx).is_ready());
+                    if idle {
+                        restart(me.heartbeat.as_mut(), *me.interval);
+                        return Poll::Ready(Some(Vec::new()));
+                    }
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
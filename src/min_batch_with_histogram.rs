@@ -0,0 +1,128 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::Stream;
+use pin_project_lite::pin_project;
+
+use crate::min_batch_with_weight::MinBatchWithWeight;
+
+/// A fixed-bucket histogram of the weights of the last `window` batches. The buckets are
+/// `bucket_width` wide, starting at `0`, and the last one also collects every heavier weight, so
+/// its inclusive range ends at `usize::MAX`, the weight of a saturated batch. Memory use is bounded by the bucket count and the window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    bucket_width: usize,
+    buckets: Vec<(RangeInclusive<usize>, usize)>,
+    window: usize,
+    recent: VecDeque<usize>,
+}
+
+impl Histogram {
+    /// A `bucket_width`, `bucket_count` or `window` of `0` behaves like `1`.
+    pub fn new(bucket_width: usize, bucket_count: usize, window: usize) -> Self {
+        let bucket_width = bucket_width.max(1);
+        let bucket_count = bucket_count.max(1);
+        let buckets = (0..bucket_count)
+            .map(|i| {
+                let start = i.saturating_mul(bucket_width);
+                let end = if i + 1 == bucket_count {
+                    usize::MAX
+                } else {
+                    start.saturating_add(bucket_width - 1)
+                };
+                (start..=end, 0)
+            })
+            .collect();
+        let window = window.max(1);
+        Histogram {
+            bucket_width,
+            buckets,
+            window,
+            recent: VecDeque::with_capacity(window),
+        }
+    }
+
+    /// Counts `weight`, forgetting the oldest weight once the window is full.
+    pub fn record(&mut self, weight: usize) {
+        let bucket = (weight / self.bucket_width).min(self.buckets.len() - 1);
+        if self.recent.len() == self.window {
+            if let Some(oldest) = self.recent.pop_front() {
+                self.buckets[oldest].1 -= 1;
+            }
+        }
+        self.recent.push_back(bucket);
+        self.buckets[bucket].1 += 1;
+    }
+
+    /// The weight range of every bucket with the number of windowed batches it holds.
+    pub fn buckets(&self) -> &[(RangeInclusive<usize>, usize)] {
+        &self.buckets
+    }
+
+    /// Number of batches in the window.
+    pub fn len(&self) -> usize {
+        self.recent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.recent.is_empty()
+    }
+}
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) and records the weight of every
+    /// yielded batch, the terminal partial one included, into a rolling [`Histogram`] available
+    /// through [`weight_histogram`](Self::weight_histogram).
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchWithHistogram<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) batches: MinBatchWithWeight<S, F, T, usize>,
+        histogram: Histogram,
+    }
+}
+
+impl<S, F, T> MinBatchWithHistogram<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, histogram: Histogram) -> Self {
+        MinBatchWithHistogram {
+            batches: MinBatchWithWeight::new(stream, min_batch_weight, count_fn),
+            histogram,
+        }
+    }
+
+    /// Histogram of the weights of the recently yielded batches.
+    pub fn weight_histogram(&self) -> &Histogram {
+        &self.histogram
+    }
+}
+
+impl<S, F, T> Stream for MinBatchWithHistogram<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let me = self.project();
+        me.batches.poll_next(cx).map(|batch| {
+            batch.map(|(items, weight)| {
+                me.histogram.record(weight);
+                items
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.batches.size_hint()
+    }
+}
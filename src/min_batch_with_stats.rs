@@ -0,0 +1,89 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+/// Statistics of the per-item weights of a batch.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BatchStats {
+    pub total: usize,
+    pub count: usize,
+    pub min: usize,
+    pub max: usize,
+}
+
+impl BatchStats {
+    fn record(&mut self, weight: usize) {
+        if self.count == 0 {
+            self.min = weight;
+            self.max = weight;
+        } else {
+            self.min = self.min.min(weight);
+            self.max = self.max.max(weight);
+        }
+        self.total = self.total.saturating_add(weight);
+        self.count += 1;
+    }
+}
+
+pin_project! {
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchWithStats<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        stats: BatchStats,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+    }
+}
+
+impl<S, F, T> MinBatchWithStats<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchWithStats {
+            stream: stream.fuse(),
+            stats: BatchStats::default(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchWithStats<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = (Vec<S::Item>, BatchStats);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match ready!(me.stream.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    let weight = (me.core.count_fn)(&item);
+                    me.stats.record(weight);
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        return Poll::Ready(Some((items, core::mem::take(me.stats))));
+                    }
+                }
+                None => {
+                    let last = me.core.finish();
+                    return Poll::Ready(last.map(|(items, _)| (items, core::mem::take(me.stats))));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use alloc::vec::Vec;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+use std::time::Duration;
+use tokio::time::{sleep, Instant, Sleep};
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) but also flushes the accumulated
+    /// items when no new item arrives within `timeout`. The timer is armed only while a batch is
+    /// pending and re-armed with every pushed item, so an idle stream with an empty buffer emits
+    /// nothing.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchWithTimeout<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        #[pin]
+        sleep: Option<Sleep>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        timeout: Duration,
+    }
+}
+
+impl<S, F, T> MinBatchWithTimeout<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, timeout: Duration) -> Self {
+        MinBatchWithTimeout {
+            stream: stream.fuse(),
+            sleep: None,
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            timeout,
+        }
+    }
+}
+
+impl<S, F, T> Stream for MinBatchWithTimeout<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            match me.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let weight = (me.core.count_fn)(&item);
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        me.sleep.set(None);
+                        return Poll::Ready(Some(items));
+                    }
+                    match me.sleep.as_mut().as_pin_mut() {
+                        Some(sleep) => sleep.reset(Instant::now() + *me.timeout),
+                        None => me.sleep.set(Some(sleep(*me.timeout))),
+                    }
+                }
+                Poll::Ready(None) => {
+                    me.sleep.set(None);
+                    return Poll::Ready(me.core.finish().map(|(items, _)| items));
+                }
+                Poll::Pending => {
+                    if let Some(sleep) = me.sleep.as_mut().as_pin_mut() {
+                        if sleep.poll(cx).is_ready() {
+                            me.sleep.set(None);
+                            if let Some((items, _)) = me.core.take_pending() {
+                                return Poll::Ready(Some(items));
+                            }
+                        }
+                    }
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
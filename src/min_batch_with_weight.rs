@@ -1,77 +1,192 @@
+use alloc::vec::Vec;
+use core::fmt;
 use core::pin::Pin;
 use core::task::{Context, Poll};
-use futures::ready;
+use futures::sink::Sink;
 use futures::stream::{Fuse, Stream};
 use futures::StreamExt;
 use pin_project_lite::pin_project;
 
+use crate::batcher::Batcher;
+use crate::weight::Weight;
+
 pin_project! {
     #[must_use = "streams do nothing unless polled"]
-    #[derive(Debug)]
-    pub struct MinBatchWithWeight<S, F, T> where
+    pub struct MinBatchWithWeight<S, F, T, W = usize> where
     S: Stream<Item = T>,
-    F: Fn(&T) -> usize,
+    F: FnMut(&T) -> W,
+    W: Weight,
 {
         #[pin]
         pub(crate) stream: Fuse<S>,
-        current_batch_weight: usize,
-        pub(crate) items: Vec<S::Item>,
-        min_batch_weight: usize,
-        count_fn: F,
+        pub(crate) core: Batcher<F, Vec<T>, W>,
     }
 }
 
-impl<S, F, T> MinBatchWithWeight<S, F, T>
+impl<S, F, T, W> MinBatchWithWeight<S, F, T, W>
 where
     S: Stream<Item = T>,
-    F: Fn(&T) -> usize,
+    F: FnMut(&T) -> W,
+    W: Weight,
 {
-    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+    pub fn new(stream: S, min_batch_weight: W, count_fn: F) -> Self {
         MinBatchWithWeight {
             stream: stream.fuse(),
-            current_batch_weight: 0,
-            items: Vec::with_capacity(min_batch_weight),
-            min_batch_weight,
-            count_fn,
+            core: Batcher::new(min_batch_weight, count_fn).reserving_last_len(),
+        }
+    }
+
+    /// Like [`MinBatchWithWeight::new`] but an item that would push the batch weight past
+    /// `max_batch_weight` flushes the current batch first and starts the next one, so such a batch
+    /// may be lighter than `min_batch_weight`. An item that alone exceeds `max_batch_weight` is
+    /// never split, it is yielded as a batch of its own with its full weight.
+    pub fn new_capped(stream: S, min_batch_weight: W, max_batch_weight: W, count_fn: F) -> Self {
+        let mut core = Batcher::new(min_batch_weight, count_fn).reserving_last_len();
+        core.max_batch_weight = Some(max_batch_weight);
+        MinBatchWithWeight {
+            stream: stream.fuse(),
+            core,
+        }
+    }
+
+    /// Weight accumulated by the batch in progress.
+    pub fn current_weight(&self) -> W {
+        self.core.current_batch_weight
+    }
+
+    /// Number of items buffered in the batch in progress.
+    pub fn pending_len(&self) -> usize {
+        self.core.buffered
+    }
+
+    /// Changes the weight threshold for the batch in progress and all the following ones. Lowering
+    /// it does not split the items already buffered: if they meet the new threshold, they are all
+    /// yielded as a single batch on the next poll.
+    pub fn set_min_batch_weight(&mut self, min_batch_weight: W) {
+        self.core.min_batch_weight = min_batch_weight;
+    }
+
+    /// Takes the batch in progress together with its weight out of the adapter, the next poll
+    /// starts a fresh batch. Returns `None` if nothing is buffered.
+    pub fn take_pending(&mut self) -> Option<(Vec<T>, W)> {
+        self.core.take_pending()
+    }
+
+    /// Discards the batch in progress, e.g. when upstream signals that partial work is to be
+    /// abandoned. The buffered items are dropped, the inner stream is left untouched and the next
+    /// poll starts a fresh batch.
+    pub fn reset(&mut self) {
+        self.core.reset();
+    }
+
+    /// Acquires a reference to the underlying stream.
+    pub fn get_ref(&self) -> &S {
+        self.stream.get_ref()
+    }
+
+    /// Acquires a mutable reference to the underlying stream.
+    ///
+    /// It is inadvisable to directly poll the underlying stream, as it bypasses the batching.
+    pub fn get_mut(&mut self) -> &mut S {
+        self.stream.get_mut()
+    }
+
+    /// Acquires a pinned mutable reference to the underlying stream.
+    ///
+    /// It is inadvisable to directly poll the underlying stream, as it bypasses the batching.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut S> {
+        self.project().stream.get_pin_mut()
+    }
+
+    /// Consumes this adapter, returning the underlying stream. The items of the batch in progress
+    /// are dropped, use [`take_pending`](Self::take_pending) first to keep them.
+    pub fn into_inner(self) -> S {
+        self.stream.into_inner()
+    }
+}
+
+/// Shows the state of the batch in progress, neither the inner stream nor the closure need to
+/// implement `Debug`.
+impl<S, F, T, W> fmt::Debug for MinBatchWithWeight<S, F, T, W>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> W,
+    W: Weight + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MinBatchWithWeight")
+            .field("min_batch_weight", &self.core.min_batch_weight)
+            .field("max_batch_weight", &self.core.max_batch_weight)
+            .field("current_batch_weight", &self.core.current_batch_weight)
+            .field("pending_len", &self.core.items.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Clones the inner stream, the closure and the batch in progress. The cloned inner stream is
+/// fused anew, so a clone of an adapter whose upstream has already finished polls it again.
+impl<S, F, T, W> Clone for MinBatchWithWeight<S, F, T, W>
+where
+    S: Stream<Item = T> + Clone,
+    F: FnMut(&T) -> W + Clone,
+    T: Clone,
+    W: Weight,
+{
+    fn clone(&self) -> Self {
+        MinBatchWithWeight {
+            stream: self.stream.get_ref().clone().fuse(),
+            core: self.core.clone(),
         }
     }
 }
 
-impl<S, F, T> Stream for MinBatchWithWeight<S, F, T>
+impl<S, F, T, W> Stream for MinBatchWithWeight<S, F, T, W>
 where
     S: Stream<Item = T>,
-    F: Fn(&T) -> usize,
+    F: FnMut(&T) -> W,
+    W: Weight,
 {
-    type Item = (Vec<S::Item>, usize);
+    type Item = (Vec<S::Item>, W);
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let mut me = self.project();
-        loop {
-            match ready!(me.stream.as_mut().poll_next(cx)) {
-                Some(item) => {
-                    if me.items.is_empty() {
-                        me.items.reserve(*me.min_batch_weight);
-                    }
-                    let new_count = (me.count_fn)(&item);
-                    me.items.push(item);
-                    *me.current_batch_weight += new_count;
-                    if *me.current_batch_weight >= *me.min_batch_weight {
-                        let batch_weight = *me.current_batch_weight;
-                        *me.current_batch_weight = 0;
-                        return Poll::Ready(Some((std::mem::take(me.items), batch_weight)));
-                    }
-                }
-                None => {
-                    let last = if me.items.is_empty() {
-                        None
-                    } else {
-                        let batch_weight = *me.current_batch_weight;
-                        *me.current_batch_weight = 0;
-                        Some((std::mem::take(me.items), batch_weight))
-                    };
-                    return Poll::Ready(last);
-                }
-            }
-        }
+        let me = self.project();
+        me.core.poll_next(me.stream, cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending_batch = usize::from(!self.core.items.is_empty());
+        let upper = self
+            .stream
+            .size_hint()
+            .1
+            .and_then(|upper| upper.checked_add(pending_batch));
+        (0, upper)
+    }
+}
+
+/// Forwards to the inner stream when it is a sink as well, so that a duplex connection stays
+/// usable for the other direction once its incoming half is batched.
+impl<S, F, T, W, Item> Sink<Item> for MinBatchWithWeight<S, F, T, W>
+where
+    S: Stream<Item = T> + Sink<Item>,
+    F: FnMut(&T) -> W,
+    W: Weight,
+{
+    type Error = S::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().stream.poll_ready(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error> {
+        self.project().stream.start_send(item)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().stream.poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().stream.poll_close(cx)
     }
 }
@@ -0,0 +1,93 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::min_batch::MinBatch;
+
+pin_project! {
+    /// Batches two streams independently like [`MinBatch`] and yields their batches in aligned
+    /// pairs, the n-th batch of one side with the n-th batch of the other.
+    ///
+    /// The side that completes its batch first waits for the other one. While its batch waits it
+    /// is not polled, so a fast side is held back by the slow one and never buffers more than a
+    /// single batch ahead. Once a side ends, the remaining batches of the other one, its partial
+    /// tail included, are paired with empty batches.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct MinBatchZip<S, O, FA, FB, A, B> where
+    S: Stream<Item = A>,
+    O: Stream<Item = B>,
+    FA: FnMut(&A) -> usize,
+    FB: FnMut(&B) -> usize,
+{
+        #[pin]
+        pub(crate) left: Fuse<MinBatch<S, FA, A>>,
+        #[pin]
+        pub(crate) right: Fuse<MinBatch<O, FB, B>>,
+        pub(crate) left_batch: Option<Vec<A>>,
+        pub(crate) right_batch: Option<Vec<B>>,
+    }
+}
+
+impl<S, O, FA, FB, A, B> MinBatchZip<S, O, FA, FB, A, B>
+where
+    S: Stream<Item = A>,
+    O: Stream<Item = B>,
+    FA: FnMut(&A) -> usize,
+    FB: FnMut(&B) -> usize,
+{
+    pub fn new(
+        stream: S,
+        other: O,
+        min_batch_weight_a: usize,
+        count_a: FA,
+        min_batch_weight_b: usize,
+        count_b: FB,
+    ) -> Self {
+        MinBatchZip {
+            left: MinBatch::new(stream, min_batch_weight_a, count_a).fuse(),
+            right: MinBatch::new(other, min_batch_weight_b, count_b).fuse(),
+            left_batch: None,
+            right_batch: None,
+        }
+    }
+}
+
+impl<S, O, FA, FB, A, B> Stream for MinBatchZip<S, O, FA, FB, A, B>
+where
+    S: Stream<Item = A>,
+    O: Stream<Item = B>,
+    FA: FnMut(&A) -> usize,
+    FB: FnMut(&B) -> usize,
+{
+    type Item = (Vec<A>, Vec<B>);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        if me.left_batch.is_none() {
+            if let Poll::Ready(Some(batch)) = me.left.as_mut().poll_next(cx) {
+                *me.left_batch = Some(batch);
+            }
+        }
+        if me.right_batch.is_none() {
+            if let Poll::Ready(Some(batch)) = me.right.as_mut().poll_next(cx) {
+                *me.right_batch = Some(batch);
+            }
+        }
+        match (me.left_batch.take(), me.right_batch.take()) {
+            (Some(left), Some(right)) => Poll::Ready(Some((left, right))),
+            (Some(left), None) if me.right.is_done() => Poll::Ready(Some((left, Vec::new()))),
+            (None, Some(right)) if me.left.is_done() => Poll::Ready(Some((Vec::new(), right))),
+            (None, None) if me.left.is_done() && me.right.is_done() => Poll::Ready(None),
+            // the side still batching was polled above and woke us up when ready
+            (left, right) => {
+                *me.left_batch = left;
+                *me.right_batch = right;
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::ready;
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+pin_project! {
+    /// Re-windows a stream of batches of arbitrary sizes into batches of at least
+    /// `min_batch_weight`, as if they were flattened and batched by
+    /// [`MinBatch`](crate::min_batch::MinBatch): small input batches are combined and large ones
+    /// are split, and the order of the items is preserved across batches.
+    ///
+    /// An input batch that doesn't complete the batch in progress is kept as it is, moved rather
+    /// than copied item by item when nothing is buffered yet. Only the tail of an input batch
+    /// that gets split is copied out.
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct Rebatch<S, F, T> where
+    S: Stream<Item = Vec<T>>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        current_batch_weight: usize,
+        pub(crate) items: Vec<T>,
+        pub(crate) rest: Vec<T>,
+        min_batch_weight: usize,
+        count_fn: F,
+    }
+}
+
+impl<S, F, T> Rebatch<S, F, T>
+where
+    S: Stream<Item = Vec<T>>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F) -> Self {
+        Rebatch {
+            stream: stream.fuse(),
+            current_batch_weight: 0,
+            items: Vec::new(),
+            rest: Vec::new(),
+            min_batch_weight,
+            count_fn,
+        }
+    }
+}
+
+impl<S, F, T> Stream for Rebatch<S, F, T>
+where
+    S: Stream<Item = Vec<T>>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        loop {
+            // the tail of a split input batch comes first
+            let mut input = if me.rest.is_empty() {
+                match ready!(me.stream.as_mut().poll_next(cx)) {
+                    Some(input) => input,
+                    None => {
+                        let last = if me.items.is_empty() {
+                            None
+                        } else {
+                            *me.current_batch_weight = 0;
+                            Some(core::mem::take(me.items))
+                        };
+                        return Poll::Ready(last);
+                    }
+                }
+            } else {
+                core::mem::take(me.rest)
+            };
+            let mut cut = None;
+            for (i, item) in input.iter().enumerate() {
+                let new_count = (me.count_fn)(item);
+                *me.current_batch_weight = me.current_batch_weight.saturating_add(new_count);
+                if me.current_batch_weight >= me.min_batch_weight {
+                    cut = Some(i + 1);
+                    break;
+                }
+            }
+            match cut {
+                Some(cut) => {
+                    *me.rest = input.split_off(cut);
+                    *me.current_batch_weight = 0;
+                    let batch = if me.items.is_empty() {
+                        input
+                    } else {
+                        me.items.append(&mut input);
+                        core::mem::take(me.items)
+                    };
+                    return Poll::Ready(Some(batch));
+                }
+                None if me.items.is_empty() => *me.items = input,
+                None => me.items.append(&mut input),
+            }
+        }
+    }
+}
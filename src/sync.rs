@@ -0,0 +1,86 @@
+//! Synchronous counterpart of the stream adapters for plain iterators, so that the same weighting
+//! closures can be shared between sync and async code.
+
+use alloc::vec::Vec;
+use core::iter::{Fuse, FusedIterator};
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Debug)]
+pub struct MinBatchIter<I, F, T>
+where
+    I: Iterator<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    iter: Fuse<I>,
+    core: Batcher<F, Vec<T>, usize>,
+}
+
+impl<I, F, T> MinBatchIter<I, F, T>
+where
+    I: Iterator<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(iter: I, min_batch_weight: usize, count_fn: F) -> Self {
+        MinBatchIter {
+            iter: iter.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+        }
+    }
+}
+
+impl<I, F, T> Iterator for MinBatchIter<I, F, T>
+where
+    I: Iterator<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for item in self.iter.by_ref() {
+            let weight = (self.core.count_fn)(&item);
+            if let Some((items, _)) = self.core.push(item, weight) {
+                return Some(items);
+            }
+        }
+        self.core.finish().map(|(items, _)| items)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (usize::from(lower > 0), upper)
+    }
+}
+
+impl<I, F, T> FusedIterator for MinBatchIter<I, F, T>
+where
+    I: Iterator<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+}
+
+/// Splits `items` into batches of at least `min_batch_weight` at once, by the very same rule as
+/// [`MinBatch`](crate::min_batch::MinBatch): the batches are those the stream adapter would
+/// yield for the same items, the last one possibly lighter.
+pub fn split_by_weight<T, F>(items: Vec<T>, min_batch_weight: usize, count_fn: F) -> Vec<Vec<T>>
+where
+    F: FnMut(&T) -> usize,
+{
+    MinBatchIter::new(items.into_iter(), min_batch_weight, count_fn).collect()
+}
+
+pub trait MinBatchIterExt: Iterator {
+    fn min_batch<F>(self, min_batch_weight: usize, count_fn: F) -> MinBatchIter<Self, F, Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> usize,
+    {
+        MinBatchIter::new(self, min_batch_weight, count_fn)
+    }
+}
+
+// Implement the trait for all types that implement Iterator
+impl<T: ?Sized> MinBatchIterExt for T where T: Iterator {}
@@ -0,0 +1,88 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::{Fuse, Stream};
+use futures::StreamExt;
+use pin_project_lite::pin_project;
+
+use crate::batcher::Batcher;
+use crate::min_batch::initial_capacity;
+
+pin_project! {
+    /// Batches like [`MinBatch`](crate::min_batch::MinBatch) for `n` batches, then yields one last
+    /// batch of the items upstream has ready and terminates.
+    ///
+    /// Unlike `.min_batch(..).take(n + 1)`, the last batch does not wait for upstream to reach
+    /// `min_batch_weight`: it is flushed, usually partial, as soon as upstream is pending, so the
+    /// items produced so far are reclaimed rather than held or dropped while waiting. The rest of
+    /// the stream is left unpolled and can be recovered by [`into_inner`](Self::into_inner).
+    #[must_use = "streams do nothing unless polled"]
+    #[derive(Debug)]
+    pub struct TakeBatches<S, F, T> where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+        #[pin]
+        pub(crate) stream: Fuse<S>,
+        pub(crate) core: Batcher<F, Vec<T>, usize>,
+        remaining: usize,
+        pub(crate) done: bool,
+    }
+}
+
+impl<S, F, T> TakeBatches<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    pub fn new(stream: S, min_batch_weight: usize, count_fn: F, n: usize) -> Self {
+        TakeBatches {
+            stream: stream.fuse(),
+            core: Batcher::new(min_batch_weight, count_fn)
+                .reserving(initial_capacity(min_batch_weight)),
+            remaining: n,
+            done: false,
+        }
+    }
+
+    /// Consumes this adapter, returning the underlying stream with the items not taken. The items
+    /// of the batch in progress are dropped.
+    pub fn into_inner(self) -> S {
+        self.stream.into_inner()
+    }
+}
+
+impl<S, F, T> Stream for TakeBatches<S, F, T>
+where
+    S: Stream<Item = T>,
+    F: FnMut(&T) -> usize,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut me = self.project();
+        if *me.done {
+            return Poll::Ready(None);
+        }
+        loop {
+            match me.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let weight = (me.core.count_fn)(&item);
+                    if let Some((items, _)) = me.core.push(item, weight) {
+                        match me.remaining.checked_sub(1) {
+                            Some(remaining) => *me.remaining = remaining,
+                            None => *me.done = true,
+                        }
+                        return Poll::Ready(Some(items));
+                    }
+                }
+                Poll::Pending if *me.remaining > 0 => return Poll::Pending,
+                // upstream is pending while the last batch is built, or it has ended
+                _ => {
+                    *me.done = true;
+                    return Poll::Ready(me.core.take_pending().map(|(items, _)| items));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures::stream::Stream;
+use futures::task::noop_waker_ref;
+
+/// Synchronously drains every item `stream` has ready right now, without an async runtime. The
+/// stream is polled with a no-op waker until it is exhausted or returns `Poll::Pending`, so it
+/// is meant for adapters over already populated sources like `stream::iter`, e.g. in property
+/// tests.
+pub fn collect_now<S>(stream: &mut S) -> Vec<S::Item>
+where
+    S: Stream + Unpin,
+{
+    let mut cx = Context::from_waker(noop_waker_ref());
+    let mut ready = Vec::new();
+    while let Poll::Ready(Some(item)) = Pin::new(&mut *stream).poll_next(&mut cx) {
+        ready.push(item);
+    }
+    ready
+}
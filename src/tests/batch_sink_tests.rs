@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use crate::batch_sink::BatchSink;
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Sum {
+        len: usize,
+        total: usize,
+    }
+
+    impl BatchSink<usize> for Sum {
+        fn push(&mut self, item: usize) {
+            self.len += 1;
+            self.total += item;
+        }
+
+        fn len(&self) -> usize {
+            self.len
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_sink() {
+        let batches: Vec<Sum> = stream::iter(vec![1, 2, 3, 1, 1])
+            .min_batch_in::<Sum, _>(3, |x: &usize| *x)
+            .collect()
+            .await;
+
+        assert_eq!(
+            batches,
+            vec![
+                Sum { len: 2, total: 3 },
+                Sum { len: 1, total: 3 },
+                Sum { len: 2, total: 2 }
+            ]
+        );
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[tokio::test]
+    async fn test_smallvec_sink_matches_vec() {
+        use smallvec::SmallVec;
+
+        let input = vec![1, 2, 3, 1, 1, 5, 2, 2];
+        let vec_batches: Vec<Vec<usize>> = stream::iter(input.clone())
+            .min_batch(3, |x: &usize| *x)
+            .collect()
+            .await;
+        let small_batches: Vec<SmallVec<[usize; 4]>> = stream::iter(input)
+            .min_batch_in(3, |x: &usize| *x)
+            .collect()
+            .await;
+
+        let small_batches: Vec<Vec<usize>> =
+            small_batches.into_iter().map(|b| b.into_vec()).collect();
+        assert_eq!(small_batches, vec_batches);
+    }
+
+    #[tokio::test]
+    async fn test_deque_batches_pop_from_front() {
+        let mut batches = stream::iter(vec![1, 2, 3, 1, 1]).min_batch_deque(3, |x: &usize| *x);
+        let mut firsts = Vec::new();
+        while let Some(mut batch) = batches.next().await {
+            firsts.push(batch.pop_front().unwrap());
+            assert_eq!(batch.len(), if firsts.len() == 2 { 0 } else { 1 });
+        }
+        assert_eq!(firsts, vec![1, 3, 1]);
+    }
+}
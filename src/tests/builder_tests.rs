@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use crate::builder::MinBatchBuilder;
+    use futures::{stream, StreamExt};
+    use std::pin::pin;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+    use tokio::time::Instant;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    #[tokio::test]
+    async fn test_min_weight_with_max_len() {
+        let batches: Vec<Vec<usize>> = MinBatchBuilder::new()
+            .min_weight(5)
+            .max_len(2)
+            .build(stream::iter(vec![1, 1, 4, 3, 1, 1, 1]), |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1, 1], vec![4, 3], vec![1, 1], vec![1]]);
+    }
+
+    #[tokio::test]
+    async fn test_max_weight_below_min_weight_wins() {
+        let batches: Vec<Vec<usize>> = MinBatchBuilder::new()
+            .min_weight(10)
+            .max_weight(4)
+            .build(stream::iter(vec![1, 2, 3, 4, 6, 1]), |x: &usize| *x)
+            .collect()
+            .await;
+        // cut before exceeding 4, an item of 6 alone exceeds it and is yielded alone
+        assert_eq!(
+            batches,
+            vec![vec![1, 2], vec![3], vec![4], vec![6], vec![1]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_policy_yields_whole_stream() {
+        let batches: Vec<Vec<i32>> = MinBatchBuilder::new()
+            .build(stream::iter(1..=4), |_: &i32| 1)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1, 2, 3, 4]]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_idle_timeout_and_deadline() {
+        let (tx, rx) = mpsc::channel(16);
+        tokio::spawn(async move {
+            // a steady trickle every 40ms, then a pause longer than the idle timeout
+            for i in 1..=4 {
+                tx.send(i).await.unwrap();
+                tokio::time::sleep(Duration::from_millis(40)).await;
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            tx.send(5).await.unwrap();
+        });
+        let start = Instant::now();
+        let mut batches = pin!(MinBatchBuilder::new()
+            .min_weight(100)
+            .idle_timeout(Duration::from_millis(60))
+            .deadline(Duration::from_millis(100))
+            .build(ReceiverStream::new(rx), |x: &usize| *x));
+
+        // the trickle never idles, the deadline cuts the batch
+        assert_eq!(batches.next().await, Some(vec![1, 2, 3]));
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+        // the item at 120ms is followed by silence, the idle timeout cuts the batch
+        assert_eq!(batches.next().await, Some(vec![4]));
+        assert_eq!(start.elapsed(), Duration::from_millis(180));
+        assert_eq!(batches.next().await, Some(vec![5]));
+        assert_eq!(batches.next().await, None);
+    }
+}
@@ -0,0 +1,23 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::stream;
+
+    #[tokio::test]
+    async fn test_reused_outer_vec_is_refilled() {
+        let mut outer = Vec::new();
+        stream::iter(1..=5)
+            .min_batch(2, |_: &i32| 1)
+            .collect_batches_into(&mut outer)
+            .await;
+        assert_eq!(outer, vec![vec![1, 2], vec![3, 4], vec![5]]);
+        let capacity = outer.capacity();
+
+        stream::iter(10..=12)
+            .min_batch(2, |_: &i32| 1)
+            .collect_batches_into(&mut outer)
+            .await;
+        assert_eq!(outer, vec![vec![10, 11], vec![12]]);
+        assert_eq!(outer.capacity(), capacity);
+    }
+}
@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_batches_processed_on_rayon_threads() {
+        let pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(2)
+                .thread_name(|i| format!("rayon-worker-{i}"))
+                .build()
+                .unwrap(),
+        );
+        let mut results: Vec<(usize, String)> = stream::iter(1..=10)
+            .min_batch(3, |_: &usize| 1)
+            .dispatch_rayon(pool, |batch: Vec<usize>| {
+                let thread = std::thread::current()
+                    .name()
+                    .unwrap_or_default()
+                    .to_string();
+                (batch.iter().sum(), thread)
+            })
+            .collect()
+            .await;
+
+        // results arrive unordered
+        results.sort();
+        let sums: Vec<usize> = results.iter().map(|(sum, _)| *sum).collect();
+        assert_eq!(sums, vec![6, 10, 15, 24]);
+        for (_, thread) in results {
+            assert!(thread.starts_with("rayon-worker-"));
+        }
+    }
+}
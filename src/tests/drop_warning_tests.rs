@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use crate::drop_warning::{set_drop_hook, DropWarning};
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use std::cell::RefCell;
+
+    thread_local! {
+        static WARNINGS: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+    }
+
+    // other tests run on other threads, each test only sees its own warnings
+    fn capture(warning: &DropWarning) {
+        WARNINGS.with(|warnings| warnings.borrow_mut().push(warning.buffered));
+    }
+
+    fn captured() -> Vec<usize> {
+        WARNINGS.with(|warnings| warnings.take())
+    }
+
+    #[tokio::test]
+    async fn test_dropping_partially_filled_adapter_warns() {
+        set_drop_hook(capture);
+        let mut batches = stream::iter(1..=5)
+            .chain(stream::pending())
+            .min_batch(3, |_: &i32| 1);
+        assert_eq!(batches.next().await, Some(vec![1, 2, 3]));
+        // cancelled while waiting for the third item of the second batch
+        assert!(futures::poll!(batches.next()).is_pending());
+        assert_eq!(batches.pending_len(), 2);
+        drop(batches);
+        assert_eq!(captured(), vec![2]);
+
+        let mut batches = stream::iter(1..=4)
+            .chain(stream::pending())
+            .min_batch_with_weight(11, |x: &i32| *x);
+        assert!(futures::poll!(batches.next()).is_pending());
+        drop(batches);
+        assert_eq!(captured(), vec![4]);
+    }
+
+    #[tokio::test]
+    async fn test_drained_adapter_does_not_warn() {
+        set_drop_hook(capture);
+        let batches: Vec<Vec<i32>> = stream::iter(1..=5)
+            .min_batch(3, |_: &i32| 1)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1, 2, 3], vec![4, 5]]);
+
+        let mut batches = stream::iter(1..=5).min_batch(3, |_: &i32| 1);
+        assert_eq!(batches.next().await, Some(vec![1, 2, 3]));
+        drop(batches);
+        assert!(captured().is_empty());
+    }
+
+    #[test]
+    fn test_warning_displays_the_default_message() {
+        let warning = DropWarning { buffered: 3 };
+        assert_eq!(
+            warning.to_string(),
+            "min-batch: adapter dropped with 3 buffered items"
+        );
+    }
+}
@@ -0,0 +1,34 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::stream;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_concurrency_is_bounded() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+        let processed = Arc::new(AtomicUsize::new(0));
+
+        stream::iter(0..20)
+            .min_batch(2, |_: &i32| 1)
+            .for_each_batch_parallel(3, |batch| {
+                let running = running.clone();
+                let max_running = max_running.clone();
+                let processed = processed.clone();
+                async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    processed.fetch_add(batch.len(), Ordering::SeqCst);
+                    running.fetch_sub(1, Ordering::SeqCst);
+                }
+            })
+            .await;
+
+        assert_eq!(processed.load(Ordering::SeqCst), 20);
+        assert_eq!(max_running.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use crate::mem_size::MemSize;
+    use futures::{stream, StreamExt};
+    use std::mem::size_of;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Point {
+        coords: [u64; 4],
+    }
+
+    #[tokio::test]
+    async fn test_fixed_size_items_batch_by_inline_size() {
+        let points: Vec<Point> = (0..10).map(|i| Point { coords: [i; 4] }).collect();
+        let batches: Vec<Vec<Point>> = stream::iter(points).min_batch_by_mem(100).collect().await;
+        // 32 bytes a point, 4 points reach 100 bytes
+        let lens: Vec<usize> = batches.iter().map(Vec::len).collect();
+        assert_eq!(lens, vec![4, 4, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_heap_memory_is_counted() {
+        let strings = vec![String::from("a"), "b".repeat(100), String::from("c")];
+        let threshold = 2 * size_of::<String>() + 50;
+        let batches: Vec<Vec<String>> = stream::iter(strings.clone())
+            .min_batch_by_mem_size(threshold)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![strings[0..2].to_vec(), strings[2..].to_vec()]);
+    }
+
+    #[test]
+    fn test_mem_size_of_containers() {
+        let mut numbers: Vec<u32> = Vec::with_capacity(8);
+        numbers.extend([1, 2, 3]);
+        assert_eq!(numbers.mem_size(), size_of::<Vec<u32>>() + 8 * 4);
+        assert_eq!(Some(7u64).mem_size(), size_of::<Option<u64>>());
+        assert_eq!(
+            Box::new(String::with_capacity(10)).mem_size(),
+            size_of::<Box<String>>() + size_of::<String>() + 10
+        );
+    }
+}
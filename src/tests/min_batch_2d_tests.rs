@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_weight_met_but_count_not_keeps_accumulating() {
+        // 1000 is reached by the first item already, yet 3 items are needed too
+        let batches: Vec<Vec<usize>> = stream::iter(vec![1500, 10, 10, 400, 400, 400, 1])
+            .min_batch_2d(1000, 3, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(
+            batches,
+            vec![vec![1500, 10, 10], vec![400, 400, 400], vec![1]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_count_met_but_weight_not_keeps_accumulating() {
+        let batches: Vec<Vec<usize>> = stream::iter(vec![1, 1, 1, 1, 5, 2])
+            .min_batch_2d(5, 2, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1, 1, 1, 1, 5], vec![2]]);
+    }
+}
@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_trailing_singleton_is_merged() {
+        let batches: Vec<Vec<i32>> = stream::iter(1..=7)
+            .min_batch_absorb_tail(3, 2, |_: &i32| 1)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1, 2, 3], vec![4, 5, 6, 7]]);
+    }
+
+    #[tokio::test]
+    async fn test_heavy_enough_tail_is_kept() {
+        let batches: Vec<Vec<i32>> = stream::iter(1..=8)
+            .min_batch_absorb_tail(3, 2, |_: &i32| 1)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8]]);
+
+        // with nothing to merge into, a light tail is still yielded
+        let batches: Vec<Vec<i32>> = stream::iter(1..=1)
+            .min_batch_absorb_tail(3, 2, |_: &i32| 1)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1]]);
+    }
+}
@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_threshold_follows_backlog() {
+        let backlog = Arc::new(AtomicUsize::new(0));
+        let mut batches = stream::iter(1..=12).min_batch_adaptive(
+            2,
+            |_: &i32| 1,
+            backlog.clone(),
+            |base, backlog| base * (1 + backlog),
+        );
+
+        assert_eq!(batches.next().await, Some(vec![1, 2]));
+        backlog.store(1, Ordering::Relaxed);
+        assert_eq!(batches.next().await, Some(vec![3, 4, 5, 6]));
+        backlog.store(2, Ordering::Relaxed);
+        assert_eq!(batches.next().await, Some(vec![7, 8, 9, 10, 11, 12]));
+    }
+
+    #[tokio::test]
+    async fn test_lowered_threshold_flushes_buffered_items() {
+        let backlog = Arc::new(AtomicUsize::new(3));
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let mut batches = rx.min_batch_adaptive(
+            1,
+            |_: &i32| 1,
+            backlog.clone(),
+            |base, backlog| base + backlog,
+        );
+        for item in 1..=3 {
+            tx.unbounded_send(item).unwrap();
+        }
+        // under the threshold of 4, nothing is yielded yet
+        assert!(futures::poll!(batches.next()).is_pending());
+        backlog.store(0, Ordering::Relaxed);
+        assert_eq!(batches.next().await, Some(vec![1, 2, 3]));
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use std::pin::pin;
+    use std::sync::Arc;
+    use tokio::sync::Notify;
+
+    #[tokio::test]
+    async fn test_async_closure_weight_awaits_notify() {
+        let notify = Arc::new(Notify::new());
+        let weigh = notify.clone();
+        let mut batches = pin!(stream::iter(vec![1, 2, 3]).min_batch_async(
+            3,
+            async move |x: &usize| {
+                weigh.notified().await;
+                *x
+            }
+        ));
+
+        // the weight of the first item is awaited
+        assert!(futures::poll!(batches.next()).is_pending());
+        notify.notify_one();
+        // the first item weighs 1, the weight of the second one is awaited
+        assert!(futures::poll!(batches.next()).is_pending());
+        notify.notify_one();
+        assert_eq!(batches.next().await, Some(vec![1, 2]));
+
+        notify.notify_one();
+        assert_eq!(batches.next().await, Some(vec![3]));
+        assert_eq!(batches.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_async_closure_borrows_item() {
+        let batches: Vec<Vec<String>> = stream::iter(["a", "bb", "ccc", "dddd"].map(String::from))
+            .min_batch_async(4, async |s: &String| {
+                tokio::task::yield_now().await;
+                s.len()
+            })
+            .collect()
+            .await;
+        assert_eq!(
+            batches,
+            vec![
+                vec!["a".to_string(), "bb".into(), "ccc".into()],
+                vec!["dddd".into()]
+            ]
+        );
+    }
+}
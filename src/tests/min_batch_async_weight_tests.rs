@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_weight_resolved_across_await_point() {
+        let batches: Vec<Vec<usize>> = stream::iter(vec![1, 2, 3, 4, 5])
+            .min_batch_async_weight(3, |x: &usize| {
+                let weight = *x;
+                async move {
+                    tokio::task::yield_now().await;
+                    weight
+                }
+            })
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1, 2], vec![3], vec![4], vec![5]]);
+    }
+
+    #[tokio::test]
+    async fn test_item_is_held_while_weight_is_pending() {
+        let (tx, rx) = futures::channel::oneshot::channel::<usize>();
+        let rx = futures::FutureExt::shared(rx);
+        let batches = stream::iter(vec![1, 2]).min_batch_async_weight(3, move |x: &usize| {
+            let weight = *x;
+            let rx = rx.clone();
+            async move {
+                rx.await.unwrap();
+                weight
+            }
+        });
+        let mut batches = std::pin::pin!(batches);
+        assert!(futures::poll!(batches.next()).is_pending());
+        tx.send(0).unwrap();
+        assert_eq!(batches.next().await, Some(vec![1, 2]));
+        assert_eq!(batches.next().await, None);
+    }
+}
@@ -0,0 +1,21 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_boxed_batches_match_vec_batches() {
+        let input = vec![1, 2, 3, 1, 1, 5, 2, 2];
+        let vec_batches: Vec<Vec<usize>> = stream::iter(input.clone())
+            .min_batch(3, |x: &usize| *x)
+            .collect()
+            .await;
+        let boxed_batches: Vec<Box<[usize]>> = stream::iter(input)
+            .min_batch_boxed(3, |x: &usize| *x)
+            .collect()
+            .await;
+        let boxed_batches: Vec<Vec<usize>> =
+            boxed_batches.into_iter().map(|b| b.into_vec()).collect();
+        assert_eq!(boxed_batches, vec_batches);
+    }
+}
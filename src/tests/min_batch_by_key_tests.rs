@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_alternating_keys_yield_singletons() {
+        let batches: Vec<Vec<(char, usize)>> =
+            stream::iter(vec![('a', 1), ('b', 1), ('a', 1), ('b', 1)])
+                .min_batch_by_key(3, |(key, _)| *key, |(_, weight)| *weight)
+                .collect()
+                .await;
+
+        assert_eq!(
+            batches,
+            vec![
+                vec![('a', 1)],
+                vec![('b', 1)],
+                vec![('a', 1)],
+                vec![('b', 1)]
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_long_run_of_same_key_is_split_by_weight() {
+        let batches: Vec<Vec<(char, usize)>> = stream::iter(vec![
+            ('a', 2),
+            ('a', 2),
+            ('a', 2),
+            ('a', 2),
+            ('a', 1),
+            ('b', 5),
+            ('c', 1),
+        ])
+        .min_batch_by_key(3, |(key, _)| *key, |(_, weight)| *weight)
+        .collect()
+        .await;
+
+        assert_eq!(
+            batches,
+            vec![
+                vec![('a', 2), ('a', 2)],
+                vec![('a', 2), ('a', 2)],
+                vec![('a', 1)],
+                vec![('b', 5)],
+                vec![('c', 1)]
+            ]
+        );
+    }
+}
@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::StreamExt;
+    use std::pin::pin;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+    use tokio_util::sync::CancellationToken;
+
+    #[tokio::test]
+    async fn test_cancel_mid_accumulation_flushes_once() {
+        let (tx, rx) = mpsc::channel(8);
+        let token = CancellationToken::new();
+        let mut batches =
+            pin!(ReceiverStream::new(rx).min_batch_cancellable(10, |x: &usize| *x, token.clone()));
+
+        tx.send(1).await.unwrap();
+        tx.send(2).await.unwrap();
+        assert!(futures::poll!(batches.next()).is_pending());
+
+        token.cancel();
+        assert_eq!(batches.next().await, Some(vec![1, 2]));
+        // the sender is still open, yet nothing else is delivered after the cancellation
+        tx.send(3).await.unwrap();
+        assert_eq!(batches.next().await, None);
+        assert_eq!(batches.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_with_empty_buffer_terminates() {
+        let (_tx, rx) = mpsc::channel::<usize>(8);
+        let token = CancellationToken::new();
+        let mut batches =
+            pin!(ReceiverStream::new(rx).min_batch_cancellable(10, |x: &usize| *x, token.clone()));
+        token.cancel();
+        assert_eq!(batches.next().await, None);
+    }
+}
@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_panic_in_count_fn_keeps_buffered_items() {
+        let mut batches =
+            stream::iter(vec![1, 2, 3, 4, 5, 6]).min_batch_catching(2, |x: &usize| {
+                assert!(*x != 4, "can't weigh {x}");
+                1
+            });
+
+        assert_eq!(batches.next().await.unwrap().unwrap(), vec![1, 2]);
+        assert_eq!(batches.next().await.unwrap().unwrap(), vec![3]);
+        let panicked = batches.next().await.unwrap().unwrap_err();
+        assert_eq!(panicked.item, 4);
+        assert_eq!(
+            panicked
+                .payload
+                .downcast_ref::<String>()
+                .map(String::as_str),
+            Some("can't weigh 4")
+        );
+        assert!(batches.next().await.is_none());
+    }
+}
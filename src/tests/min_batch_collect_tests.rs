@@ -0,0 +1,25 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use std::collections::BTreeSet;
+
+    #[tokio::test]
+    async fn test_string_batches_from_chars() {
+        let batches: Vec<String> = stream::iter("hello world".chars())
+            .min_batch_collect::<String, _>(4, |c: &char| c.len_utf8())
+            .collect()
+            .await;
+        assert_eq!(batches, vec!["hell", "o wo", "rld"]);
+    }
+
+    #[tokio::test]
+    async fn test_set_batches_keep_weight_of_every_item() {
+        // duplicates collapse in the set but still count towards the weight
+        let batches: Vec<BTreeSet<i32>> = stream::iter(vec![1, 1, 2, 3, 3, 3])
+            .min_batch_collect::<BTreeSet<i32>, _>(3, |_: &i32| 1)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![BTreeSet::from([1, 2]), BTreeSet::from([3])]);
+    }
+}
@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use crate::min_batch_credited::Credits;
+    use futures::{stream, StreamExt};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_stalls_without_credits_and_resumes() {
+        let credits = Arc::new(Credits::new(0));
+        let mut batches = stream::iter(1..=5).min_batch_credited(2, |_: &i32| 1, credits.clone());
+        assert!(futures::poll!(batches.next()).is_pending());
+
+        // a single credit pulls a single item, not enough for a batch
+        credits.add(1);
+        assert!(futures::poll!(batches.next()).is_pending());
+        assert_eq!(credits.available(), 0);
+
+        credits.add(3);
+        assert_eq!(batches.next().await, Some(vec![1, 2]));
+        assert_eq!(batches.next().await, Some(vec![3, 4]));
+        assert!(futures::poll!(batches.next()).is_pending());
+
+        credits.add(10);
+        assert_eq!(batches.next().await, Some(vec![5]));
+        assert_eq!(batches.next().await, None);
+        assert_eq!(credits.available(), 9);
+    }
+
+    #[tokio::test]
+    async fn test_granting_credits_wakes_stalled_task() {
+        let credits = Arc::new(Credits::new(0));
+        let batches = stream::iter(1..=4).min_batch_credited(2, |_: &i32| 1, credits.clone());
+        let consumer = tokio::spawn(batches.collect::<Vec<Vec<i32>>>());
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!consumer.is_finished());
+
+        credits.add(5);
+        assert_eq!(consumer.await.unwrap(), vec![vec![1, 2], vec![3, 4]]);
+    }
+}
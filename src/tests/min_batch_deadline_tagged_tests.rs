@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::StreamExt;
+    use std::pin::pin;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+    use tokio::time::Instant;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_slow_batch_is_tagged_as_timed_out() {
+        let (tx, rx) = mpsc::channel(8);
+        tokio::spawn(async move {
+            // a fast burst filling a batch, then a slow trickle
+            for i in [5, 5, 1, 1, 1] {
+                tx.send(i).await.unwrap();
+                if i == 1 {
+                    tokio::time::sleep(Duration::from_millis(40)).await;
+                }
+            }
+        });
+        let start = Instant::now();
+        let mut batches = pin!(ReceiverStream::new(rx).min_batch_deadline_tagged(
+            10,
+            |x: &usize| *x,
+            Duration::from_millis(100),
+        ));
+
+        assert_eq!(batches.next().await, Some((vec![5, 5], false)));
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        assert_eq!(batches.next().await, Some((vec![1, 1, 1], true)));
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+
+        assert_eq!(batches.next().await, None);
+    }
+}
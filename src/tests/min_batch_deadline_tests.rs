@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::StreamExt;
+    use std::pin::pin;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+    use tokio::time::Instant;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_trickle_flushed_at_deadline_anchored_to_batch_start() {
+        let (tx, rx) = mpsc::channel(8);
+        tokio::spawn(async move {
+            for i in 1..=6 {
+                tx.send(i).await.unwrap();
+                tokio::time::sleep(Duration::from_millis(40)).await;
+            }
+        });
+        let start = Instant::now();
+        let mut batches = pin!(ReceiverStream::new(rx).min_batch_deadline(
+            100,
+            |x: &usize| *x,
+            Duration::from_millis(100),
+        ));
+
+        // items keep arriving every 40ms, an idle timeout of 100ms would never fire
+        assert_eq!(batches.next().await, Some(vec![1, 2, 3]));
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+
+        // the deadline of the second batch starts with its first item at 120ms
+        assert_eq!(batches.next().await, Some(vec![4, 5, 6]));
+        assert_eq!(start.elapsed(), Duration::from_millis(220));
+
+        assert_eq!(batches.next().await, None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_weight_still_flushes_before_deadline() {
+        let (tx, rx) = mpsc::channel(8);
+        let mut batches = pin!(ReceiverStream::new(rx).min_batch_deadline(
+            3,
+            |x: &usize| *x,
+            Duration::from_secs(1),
+        ));
+        tx.send(1).await.unwrap();
+        tx.send(2).await.unwrap();
+        assert_eq!(batches.next().await, Some(vec![1, 2]));
+        drop(tx);
+        assert_eq!(batches.next().await, None);
+    }
+}
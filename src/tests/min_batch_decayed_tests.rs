@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_decay_moves_flush_boundaries() {
+        let input = vec![4, 4, 4, 4, 1, 6];
+        let decayed: Vec<Vec<usize>> = stream::iter(input.clone())
+            .min_batch_decayed(7.0, 0.5, |x: &usize| *x)
+            .collect()
+            .await;
+        // 4, then 4 * 0.5 + 4 = 6, then 6 * 0.5 + 4 = 7
+        assert_eq!(decayed, vec![vec![4, 4, 4], vec![4, 1, 6]]);
+
+        let plain: Vec<Vec<usize>> = stream::iter(input)
+            .min_batch(7, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(plain, vec![vec![4, 4], vec![4, 4], vec![1, 6]]);
+    }
+
+    #[tokio::test]
+    async fn test_no_decay_batches_like_min_batch() {
+        let input = vec![3, 0, 1, 7, 2, 2, 0, 5, 1, 1, 4];
+        for threshold in 0..10 {
+            let decayed: Vec<Vec<usize>> = stream::iter(input.clone())
+                .min_batch_decayed(threshold as f64, 1.0, |x: &usize| *x)
+                .collect()
+                .await;
+            let plain: Vec<Vec<usize>> = stream::iter(input.clone())
+                .min_batch(threshold, |x: &usize| *x)
+                .collect()
+                .await;
+            assert_eq!(decayed, plain);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_threshold_buffers_until_the_end() {
+        // items weighing 1 at a decay of 0.5 keep the accumulator under 2, short of 3
+        let batches: Vec<Vec<usize>> = stream::iter(vec![1; 50])
+            .min_batch_decayed(3.0, 0.5, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1; 50]]);
+
+        let capped: Vec<Vec<usize>> = stream::iter(vec![1; 50])
+            .min_batch_decayed_capped(3.0, 0.5, 20, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(capped, vec![vec![1; 20], vec![1; 20], vec![1; 10]]);
+    }
+}
@@ -0,0 +1,15 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_duplicates_are_dropped_within_a_batch() {
+        let batches: Vec<Vec<usize>> = stream::iter(vec![2, 2, 1, 2, 1, 3, 1, 1])
+            .min_batch_dedup(4, |x: &usize| *x)
+            .collect()
+            .await;
+        // the repeated 2s of the first batch add no weight, duplicates across batches are kept
+        assert_eq!(batches, vec![vec![2, 1, 3], vec![1]]);
+    }
+}
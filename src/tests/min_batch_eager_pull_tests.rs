@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_pulls_ahead_of_a_paused_consumer() {
+        let pulled = Arc::new(AtomicUsize::new(0));
+        let counter = pulled.clone();
+        let upstream = stream::iter(0..20).inspect(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let mut batches = upstream.min_batch_eager_pull(2, |_: &i32| 1, 8);
+
+        // the consumer does not poll, the buffer fills up to its capacity and no further
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(pulled.load(Ordering::SeqCst), 8);
+
+        // the buffered items coalesce into the first batch
+        assert_eq!(batches.next().await, Some((0..8).collect::<Vec<_>>()));
+        let rest: Vec<i32> = batches.collect::<Vec<_>>().await.concat();
+        assert_eq!(rest, (8..20).collect::<Vec<_>>());
+        assert_eq!(pulled.load(Ordering::SeqCst), 20);
+    }
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_coalesces_only_what_is_already_buffered() {
+        let batches = stream::repeat(1u8).min_batch_eager_pull(1, |_: &u8| 1, 4);
+
+        // the task keeps refilling the buffer, yet every batch ends with the items buffered before
+        let batches: Vec<Vec<u8>> =
+            tokio::time::timeout(Duration::from_secs(2), batches.take(3).collect())
+                .await
+                .expect("an always ready upstream must not hold a batch back");
+        assert_eq!(batches.len(), 3);
+        assert!(batches
+            .iter()
+            .all(|batch| !batch.is_empty() && batch.len() <= 5));
+    }
+}
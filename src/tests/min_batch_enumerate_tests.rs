@@ -0,0 +1,16 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_contiguous_batch_indices() {
+        let mut batches = stream::iter(1..=8).min_batch_enumerate(3, |_: &i32| 1);
+        assert_eq!(batches.next().await, Some((0, vec![1, 2, 3])));
+        assert_eq!(batches.next().await, Some((1, vec![4, 5, 6])));
+        assert_eq!(batches.next().await, Some((2, vec![7, 8])));
+        // no phantom index once terminated
+        assert_eq!(batches.next().await, None);
+        assert_eq!(batches.next().await, None);
+    }
+}
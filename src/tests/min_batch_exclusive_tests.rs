@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_inclusive_and_exclusive_on_same_input() {
+        let input = vec![1, 1, 1, 2, 5, 1];
+
+        let inclusive: Vec<Vec<usize>> = stream::iter(input.clone())
+            .min_batch(3, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(inclusive, vec![vec![1, 1, 1], vec![2, 5], vec![1]]);
+
+        // the item reaching the threshold opens the next batch, 5 meets it alone
+        let exclusive: Vec<Vec<usize>> = stream::iter(input)
+            .min_batch_exclusive(3, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(
+            exclusive,
+            vec![vec![1, 1], vec![1], vec![2], vec![5], vec![1]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_heavy_first_item_is_emitted_alone() {
+        let batches: Vec<Vec<usize>> = stream::iter(vec![7, 10, 1])
+            .min_batch_exclusive(3, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![7], vec![10], vec![1]]);
+    }
+}
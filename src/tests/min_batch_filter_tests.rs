@@ -0,0 +1,23 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_odd_weighted_items_are_skipped() {
+        let batches: Vec<Vec<usize>> = stream::iter(1..=11)
+            .min_batch_filter(6, |n: &usize| n.is_multiple_of(2).then_some(*n))
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![2, 4], vec![6], vec![8], vec![10]]);
+    }
+
+    #[tokio::test]
+    async fn test_all_items_skipped() {
+        let batches: Vec<Vec<i32>> = stream::iter(1..=5)
+            .min_batch_filter(2, |_: &i32| None)
+            .collect()
+            .await;
+        assert!(batches.is_empty());
+    }
+}
@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_sum_per_batch() {
+        let totals: Vec<usize> = stream::iter(vec![1, 2, 3, 1, 1, 1, 4])
+            .min_batch_fold(3, |x: &usize| *x, 0, |acc, x| acc + x)
+            .collect()
+            .await;
+
+        assert_eq!(totals, vec![3, 3, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_accumulator_resets_between_batches() {
+        let batches: Vec<String> = stream::iter(vec!["ab", "c", "def", "g"])
+            .min_batch_fold(
+                3,
+                |s: &&str| s.len(),
+                String::new(),
+                |mut acc, s| {
+                    acc.push_str(s);
+                    acc
+                },
+            )
+            .collect()
+            .await;
+
+        assert_eq!(batches, vec!["abc", "def", "g"]);
+    }
+}
@@ -0,0 +1,25 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use crate::min_batch_for_cpus::{available_cpus, weight_per_cpu};
+    use futures::{stream, StreamExt};
+
+    #[test]
+    fn test_weight_per_cpu() {
+        assert_eq!(weight_per_cpu(1000, 4), 250);
+        assert_eq!(weight_per_cpu(1000, 0), 1000);
+        assert_eq!(weight_per_cpu(3, 4), 0);
+        assert!(available_cpus() >= 1);
+    }
+
+    #[tokio::test]
+    async fn test_one_batch_per_cpu() {
+        let cpus = available_cpus();
+        let batches: Vec<Vec<usize>> = stream::iter(0..cpus * 10)
+            .min_batch_for_cpus(cpus * 10, |_: &usize| 1)
+            .collect()
+            .await;
+        assert_eq!(batches.len(), cpus);
+        assert!(batches.iter().all(|batch| batch.len() == 10));
+    }
+}
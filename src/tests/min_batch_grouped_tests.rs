@@ -0,0 +1,23 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn test_repeated_keys_are_grouped() {
+        let pairs = vec![("a", 1), ("b", 2), ("a", 3), ("a", 4), ("c", 5), ("b", 6)];
+        let batches: Vec<HashMap<&str, Vec<i32>>> = stream::iter(pairs)
+            .min_batch_grouped(4, |_: &(&str, i32)| 1)
+            .collect()
+            .await;
+        // four pairs meet the threshold even though they only have two distinct keys
+        assert_eq!(
+            batches,
+            vec![
+                HashMap::from([("a", vec![1, 3, 4]), ("b", vec![2])]),
+                HashMap::from([("c", vec![5]), ("b", vec![6])]),
+            ]
+        );
+    }
+}
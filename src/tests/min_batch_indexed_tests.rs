@@ -0,0 +1,24 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_triangular_flush_boundaries() {
+        // the n-th item of a batch weighs n, so a batch of n items weighs the n-th triangular number
+        let batches: Vec<Vec<usize>> = stream::iter(0..7)
+            .min_batch_indexed(6, |_, idx| idx + 1)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+
+        let batches: Vec<Vec<usize>> = stream::iter(0..10)
+            .min_batch_indexed(10, |_, idx| idx + 1)
+            .collect()
+            .await;
+        assert_eq!(
+            batches,
+            vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![8, 9]]
+        );
+    }
+}
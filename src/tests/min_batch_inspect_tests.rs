@@ -0,0 +1,25 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use std::cell::RefCell;
+
+    #[tokio::test]
+    async fn test_inspect_sees_yielded_batches() {
+        let inspected = RefCell::new(Vec::new());
+        let batches: Vec<Vec<usize>> = stream::iter(vec![1, 2, 3, 1, 1])
+            .min_batch_inspect(
+                3,
+                |x: &usize| *x,
+                |items: &[usize], weight| inspected.borrow_mut().push((items.to_vec(), weight)),
+            )
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1, 2], vec![3], vec![1, 1]]);
+        // the terminal partial batch is inspected too
+        assert_eq!(
+            inspected.into_inner(),
+            vec![(vec![1, 2], 3), (vec![3], 3), (vec![1, 1], 2)]
+        );
+    }
+}
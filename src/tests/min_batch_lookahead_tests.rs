@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_fit_fn_keeps_batches_under_soft_cap() {
+        let input = vec![5, 5, 5, 3, 9, 4];
+        let default_batches: Vec<Vec<usize>> = stream::iter(input.clone())
+            .min_batch(10, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(default_batches, vec![vec![5, 5], vec![5, 3, 9], vec![4]]);
+
+        // the next item is only included while the batch stays within 12
+        let batches: Vec<Vec<usize>> = stream::iter(input)
+            .min_batch_lookahead(10, |x: &usize| *x, |current, next| current + next <= 12)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![5, 5], vec![5, 3], vec![9], vec![4]]);
+    }
+
+    #[tokio::test]
+    async fn test_heavy_first_item_is_always_included() {
+        let batches: Vec<Vec<usize>> = stream::iter(vec![20, 1])
+            .min_batch_lookahead(10, |x: &usize| *x, |current, next| current + next <= 12)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![20], vec![1]]);
+    }
+
+    #[tokio::test]
+    async fn test_terminates_over_unfused_stream() {
+        use futures::stream::FusedStream;
+        use std::task::Poll;
+
+        // `poll_fn` is not a `FusedStream`, the adapter is fused nonetheless
+        let mut items = vec![3, 2, 1];
+        let mut batches = stream::poll_fn(move |_| Poll::Ready(items.pop())).min_batch_lookahead(
+            4,
+            |x: &usize| *x,
+            |current, next| current + next <= 4,
+        );
+        assert!(!batches.is_terminated());
+        assert_eq!(batches.next().await, Some(vec![1, 2]));
+        assert_eq!(batches.next().await, Some(vec![3]));
+        assert_eq!(batches.next().await, None);
+        assert!(batches.is_terminated());
+    }
+}
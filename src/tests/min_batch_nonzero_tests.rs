@@ -0,0 +1,22 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use std::num::NonZeroUsize;
+
+    #[tokio::test]
+    async fn test_empty_payloads_still_make_progress() {
+        let payloads = vec!["", "ab", "", "", "abcd", ""];
+        // weigh by byte length, but at least 1 so that empty payloads can't stall a batch
+        let batches: Vec<Vec<&str>> = stream::iter(payloads)
+            .min_batch_nonzero(3, |p: &&str| {
+                NonZeroUsize::new(p.len()).unwrap_or(NonZeroUsize::MIN)
+            })
+            .collect()
+            .await;
+        assert_eq!(
+            batches,
+            vec![vec!["", "ab"], vec!["", "", "abcd"], vec![""]]
+        );
+    }
+}
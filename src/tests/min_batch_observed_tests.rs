@@ -0,0 +1,22 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use std::cell::RefCell;
+
+    #[tokio::test]
+    async fn test_on_flush_fires_for_every_batch() {
+        let flushes = RefCell::new(Vec::new());
+        let batches: Vec<Vec<usize>> = stream::iter(vec![1, 2, 3, 1, 1])
+            .min_batch_observed(
+                3,
+                |x: &usize| *x,
+                |len, weight| flushes.borrow_mut().push((len, weight)),
+            )
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1, 2], vec![3], vec![1, 1]]);
+        // the terminal partial batch is reported too
+        assert_eq!(flushes.into_inner(), vec![(2, 3), (1, 3), (2, 2)]);
+    }
+}
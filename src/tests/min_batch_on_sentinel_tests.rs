@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[derive(Debug, PartialEq)]
+    enum Msg {
+        Data(usize),
+        Flush,
+    }
+
+    #[tokio::test]
+    async fn test_sentinel_forces_early_flush_and_is_dropped() {
+        use Msg::*;
+        let input = vec![
+            Data(1),
+            Data(1),
+            Flush,
+            Flush,
+            Data(5),
+            Data(1),
+            Data(1),
+            Flush,
+        ];
+        let batches: Vec<Vec<Msg>> = stream::iter(input)
+            .min_batch_on_sentinel(
+                5,
+                |msg: &Msg| match msg {
+                    Data(weight) => *weight,
+                    Flush => unreachable!("sentinels are never weighed"),
+                },
+                |msg: &Msg| *msg == Flush,
+            )
+            .collect()
+            .await;
+        assert_eq!(
+            batches,
+            vec![
+                vec![Data(1), Data(1)],
+                vec![Data(5)],
+                vec![Data(1), Data(1)]
+            ]
+        );
+    }
+}
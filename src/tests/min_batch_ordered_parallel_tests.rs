@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_outputs_keep_batch_order() {
+        let completed = std::sync::Mutex::new(Vec::new());
+        let sums: Vec<i32> = stream::iter(1..=8)
+            .min_batch_ordered_parallel(
+                2,
+                |_: &i32| 1,
+                3,
+                |batch: Vec<i32>| {
+                    let completed = &completed;
+                    async move {
+                        // the middle batches are the slowest
+                        let delay = if batch[0] == 3 || batch[0] == 5 {
+                            50
+                        } else {
+                            10
+                        };
+                        tokio::time::sleep(Duration::from_millis(delay)).await;
+                        completed.lock().unwrap().push(batch[0]);
+                        batch.iter().sum::<i32>()
+                    }
+                },
+            )
+            .collect()
+            .await;
+
+        assert_eq!(sums, vec![3, 7, 11, 15]);
+        // the batches did run concurrently and completed out of order
+        assert_eq!(completed.into_inner().unwrap(), vec![1, 7, 3, 5]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_concurrency_counts_reordered_outputs() {
+        let running = AtomicUsize::new(0);
+        let max_running = AtomicUsize::new(0);
+        let outputs: Vec<usize> = stream::iter(0..12)
+            .min_batch_ordered_parallel(
+                1,
+                |_: &usize| 1,
+                2,
+                |batch: Vec<usize>| {
+                    let (running, max_running) = (&running, &max_running);
+                    async move {
+                        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        max_running.fetch_max(now, Ordering::SeqCst);
+                        let delay = if batch[0].is_multiple_of(2) { 30 } else { 5 };
+                        tokio::time::sleep(Duration::from_millis(delay)).await;
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        batch[0]
+                    }
+                },
+            )
+            .collect()
+            .await;
+
+        assert_eq!(outputs, (0..12).collect::<Vec<_>>());
+        assert_eq!(max_running.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use crate::min_batch_pooled::BatchPool;
+    use futures::task::noop_waker_ref;
+    use futures::{stream, StreamExt};
+    use std::task::{Context, Poll};
+
+    #[tokio::test]
+    async fn test_pooled_batches() {
+        let pool = BatchPool::new(4);
+        let batches: Vec<Vec<i32>> = stream::iter(1..=7)
+            .min_batch_pooled(3, |_: &i32| 1, pool.clone())
+            .map(|batch| batch.to_vec())
+            .collect()
+            .await;
+
+        assert_eq!(batches, vec![vec![1, 2, 3], vec![4, 5, 6], vec![7]]);
+        // every buffer came back, the first batch returned its buffer before the second one
+        // took it, so a single buffer went around
+        assert_eq!(pool.free_len(), 1);
+    }
+
+    #[test]
+    fn test_recycle_and_into_inner() {
+        let pool = BatchPool::new(2);
+        let mut adapter = stream::iter(1..=4).min_batch_pooled(2, |_: &i32| 1, pool.clone());
+        let mut cx = Context::from_waker(noop_waker_ref());
+
+        let Poll::Ready(Some(first)) = adapter.poll_next_unpin(&mut cx) else {
+            panic!("expected a batch")
+        };
+        let Poll::Ready(Some(second)) = adapter.poll_next_unpin(&mut cx) else {
+            panic!("expected a batch")
+        };
+        assert_eq!(first.into_inner(), vec![1, 2]);
+        assert_eq!(pool.free_len(), 0);
+        second.recycle();
+        assert_eq!(pool.free_len(), 1);
+        assert!(pool.get().is_empty());
+    }
+}
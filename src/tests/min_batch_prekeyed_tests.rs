@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_supplied_weights_match_count_fn() {
+        let words = vec!["a", "bb", "c", "dddd", "ee", "f"];
+        let by_count_fn: Vec<Vec<&str>> = stream::iter(words.clone())
+            .min_batch(3, |w: &&str| w.len())
+            .collect()
+            .await;
+        let prekeyed: Vec<Vec<&str>> = stream::iter(words.iter().map(|w| (*w, w.len())))
+            .min_batch_prekeyed(3)
+            .collect()
+            .await;
+        assert_eq!(
+            prekeyed,
+            vec![vec!["a", "bb"], vec!["c", "dddd"], vec!["ee", "f"]]
+        );
+        assert_eq!(prekeyed, by_count_fn);
+
+        let tagged: Vec<Vec<(&str, usize)>> = stream::iter(words.iter().map(|w| (*w, w.len())))
+            .min_batch_prekeyed_tagged(3)
+            .collect()
+            .await;
+        assert_eq!(tagged[0], vec![("a", 1), ("bb", 2)]);
+        assert_eq!(tagged.len(), 3);
+    }
+}
@@ -0,0 +1,32 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use std::ops::Range;
+
+    #[tokio::test]
+    async fn test_ranges_are_contiguous_and_match_batch_lengths() {
+        let input = vec![2, 1, 1, 1, 5, 1, 1];
+        let batches: Vec<(Vec<usize>, Range<usize>)> = stream::iter(input.clone())
+            .min_batch_ranged(3, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(
+            batches,
+            vec![
+                (vec![2, 1], 0..2),
+                (vec![1, 1, 5], 2..5),
+                (vec![1, 1], 5..7),
+            ]
+        );
+
+        let mut next = 0;
+        for (batch, range) in &batches {
+            assert_eq!(range.start, next);
+            assert_eq!(range.len(), batch.len());
+            assert_eq!(&input[range.clone()], batch.as_slice());
+            next = range.end;
+        }
+        assert_eq!(next, input.len());
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use std::pin::pin;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+    use tokio::time::Instant;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_batches_spaced_by_min_interval() {
+        let (tx, rx) = mpsc::channel(32);
+        tokio::spawn(async move {
+            for i in 1..=12 {
+                tx.send(i).await.unwrap();
+                tokio::time::sleep(Duration::from_millis(30)).await;
+            }
+        });
+        let min_interval = Duration::from_millis(100);
+        let mut batches =
+            pin!(ReceiverStream::new(rx).min_batch_rate_limited(2, |_: &usize| 1, min_interval));
+
+        let mut emitted = Vec::new();
+        while let Some(batch) = batches.next().await {
+            emitted.push((Instant::now(), batch));
+        }
+        for pair in emitted.windows(2) {
+            assert!(pair[1].0 - pair[0].0 >= min_interval);
+        }
+        let items: Vec<usize> = emitted.into_iter().flat_map(|(_, batch)| batch).collect();
+        assert_eq!(items, (1..=12).collect::<Vec<usize>>());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_items_accumulate_while_waiting() {
+        let start = Instant::now();
+        let mut batches = pin!(stream::iter(1..=7).min_batch_rate_limited(
+            2,
+            |_: &i32| 1,
+            Duration::from_millis(100)
+        ));
+        assert_eq!(batches.next().await, Some(vec![1, 2]));
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        // the remaining ready items all join the delayed batch, the final partial batch included
+        assert_eq!(batches.next().await, Some(vec![3, 4, 5, 6, 7]));
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+        assert_eq!(batches.next().await, None);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_always_ready_upstream_does_not_starve_the_timer() {
+        let batches = stream::repeat(1u8)
+            .min_batch_rate_limited(2, |_: &u8| 1, Duration::from_millis(50))
+            .take(3)
+            .collect::<Vec<_>>();
+        let batches = tokio::time::timeout(Duration::from_secs(2), batches)
+            .await
+            .expect("the delayed batch was never yielded");
+        assert_eq!(batches.len(), 3);
+    }
+}
@@ -0,0 +1,28 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_batches_borrow_source_without_cloning() {
+        let data: Vec<String> = vec!["a".into(), "bb".into(), "ccc".into(), "d".into()];
+        let batches: Vec<Vec<&String>> = stream::iter(data.iter())
+            .min_batch_ref(3, |s: &String| s.len())
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec!["a", "bb"], vec!["ccc"], vec!["d"]]);
+        for (batched, original) in batches.iter().flatten().zip(data.iter()) {
+            assert!(std::ptr::eq(*batched, original));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unsized_pointee() {
+        let text = String::from("one two three four");
+        let batches: Vec<Vec<&str>> = stream::iter(text.split(' '))
+            .min_batch_ref(6, str::len)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec!["one", "two"], vec!["three", "four"]]);
+    }
+}
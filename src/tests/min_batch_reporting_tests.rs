@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn test_receiver_observes_one_weight_per_batch() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let batches: Vec<Vec<usize>> = stream::iter(vec![2, 2, 3, 1, 1, 5, 1])
+            .min_batch_reporting(4, |n: &usize| *n, tx)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![2, 2], vec![3, 1], vec![1, 5], vec![1]]);
+
+        let mut weights = Vec::new();
+        while let Some(weight) = rx.recv().await {
+            weights.push(weight);
+        }
+        assert_eq!(weights, vec![4, 4, 6, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_closed_receiver_is_ignored() {
+        let (tx, rx) = mpsc::unbounded_channel();
+        drop(rx);
+        let batches: Vec<Vec<i32>> = stream::iter(1..=5)
+            .min_batch_reporting(2, |_: &i32| 1, tx)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1, 2], vec![3, 4], vec![5]]);
+    }
+}
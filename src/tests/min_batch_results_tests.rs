@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_error_flushes_pending_batch_and_terminates() {
+        let batches: Vec<Result<Vec<usize>, &str>> =
+            stream::iter(vec![Ok(1), Ok(2), Ok(1), Ok(1), Err("boom"), Ok(5)])
+                .min_batch_results(3, |x: &usize| *x)
+                .collect()
+                .await;
+
+        assert_eq!(batches, vec![Ok(vec![1, 2]), Ok(vec![1, 1]), Err("boom")]);
+    }
+
+    #[tokio::test]
+    async fn test_error_as_first_item() {
+        let batches: Vec<Result<Vec<usize>, &str>> =
+            stream::iter(vec![Err("boom"), Ok(5), Err("again")])
+                .min_batch_results(3, |x: &usize| *x)
+                .collect()
+                .await;
+
+        assert_eq!(batches, vec![Err("boom")]);
+    }
+
+    #[tokio::test]
+    async fn test_without_errors() {
+        let batches: Vec<Result<Vec<usize>, &str>> = stream::iter(vec![Ok(3), Ok(1)])
+            .min_batch_results(3, |x: &usize| *x)
+            .collect()
+            .await;
+
+        assert_eq!(batches, vec![Ok(vec![3]), Ok(vec![1])]);
+    }
+
+    #[tokio::test]
+    async fn test_continue_after_transient_error() {
+        use crate::min_batch_results::ErrorPolicy;
+
+        let items = vec![
+            Ok(1),
+            Ok(1),
+            Err("timeout"),
+            Ok(2),
+            Ok(1),
+            Err("again"),
+            Ok(3),
+        ];
+        let batches: Vec<Result<Vec<usize>, &str>> = stream::iter(items.clone())
+            .try_min_batch(3, |x: &usize| *x, ErrorPolicy::Continue)
+            .collect()
+            .await;
+        assert_eq!(
+            batches,
+            vec![
+                Ok(vec![1, 1]),
+                Err("timeout"),
+                Ok(vec![2, 1]),
+                Err("again"),
+                Ok(vec![3])
+            ]
+        );
+
+        let aborted: Vec<Result<Vec<usize>, &str>> = stream::iter(items)
+            .try_min_batch(3, |x: &usize| *x, ErrorPolicy::Abort)
+            .collect()
+            .await;
+        assert_eq!(aborted, vec![Ok(vec![1, 1]), Err("timeout")]);
+    }
+}
@@ -0,0 +1,26 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_shared_batches_match_vec_batches() {
+        let input = vec![1, 2, 3, 1, 1, 5, 2, 2];
+        let vec_batches: Vec<Vec<usize>> = stream::iter(input.clone())
+            .min_batch(3, |x: &usize| *x)
+            .collect()
+            .await;
+        let shared_batches: Vec<Arc<[usize]>> = stream::iter(input)
+            .min_batch_shared(3, |x: &usize| *x)
+            .collect()
+            .await;
+        for (shared, batch) in shared_batches.iter().zip(&vec_batches) {
+            let clone = Arc::clone(shared);
+            assert!(Arc::ptr_eq(&clone, shared));
+            assert_eq!(&*clone, batch.as_slice());
+            assert_eq!(&**shared, batch.as_slice());
+        }
+        assert_eq!(shared_batches.len(), vec_batches.len());
+    }
+}
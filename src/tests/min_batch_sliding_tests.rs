@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_consecutive_windows_share_tail() {
+        let windows: Vec<Vec<i32>> = stream::iter(1..=10)
+            .min_batch_sliding(4, 2, |_: &i32| 1)
+            .collect()
+            .await;
+        assert_eq!(
+            windows,
+            vec![
+                vec![1, 2, 3, 4],
+                vec![3, 4, 5, 6],
+                vec![5, 6, 7, 8],
+                vec![7, 8, 9, 10],
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_windows_meet_threshold_with_weighed_overlap() {
+        let windows: Vec<Vec<usize>> = stream::iter(vec![3, 1, 2, 4, 1, 1, 5, 2])
+            .min_batch_sliding(6, 3, |x: &usize| *x)
+            .collect()
+            .await;
+        // the tail of [4, 1, 1] weighing 3 would be the whole window, so only [1, 1] is kept
+        assert_eq!(
+            windows,
+            vec![
+                vec![3, 1, 2],
+                vec![1, 2, 4],
+                vec![4, 1, 1],
+                vec![1, 1, 5],
+                vec![5, 2],
+            ]
+        );
+        assert!(windows.iter().all(|w| w.iter().sum::<usize>() >= 6));
+        for (pair, shared) in windows.windows(2).zip([2, 1, 2, 1]) {
+            assert!(pair[0].ends_with(&pair[1][..shared]));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_no_overlap_batches_like_min_batch() {
+        let input = vec![3, 0, 1, 7, 2, 2, 0, 5, 1, 1, 4];
+        let windows: Vec<Vec<usize>> = stream::iter(input.clone())
+            .min_batch_sliding(5, 0, |x: &usize| *x)
+            .collect()
+            .await;
+        let batches: Vec<Vec<usize>> = stream::iter(input)
+            .min_batch(5, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(windows, batches);
+    }
+}
@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_batches_sorted_with_boundaries_by_weight() {
+        let input = vec![5, 3, 9, 1, 4, 8, 2, 7, 6];
+        let sorted: Vec<Vec<usize>> = stream::iter(input.clone())
+            .min_batch_sorted_by_key(10, |x: &usize| *x, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(sorted, vec![vec![3, 5, 9], vec![1, 4, 8], vec![2, 6, 7]]);
+
+        // the same items land in the same batches as without sorting
+        let batches: Vec<Vec<usize>> = stream::iter(input)
+            .min_batch(10, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![5, 3, 9], vec![1, 4, 8], vec![2, 7, 6]]);
+        for (mut batch, sorted) in batches.into_iter().zip(&sorted) {
+            batch.sort();
+            assert_eq!(&batch, sorted);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sort_is_stable() {
+        let sorted: Vec<Vec<(u8, char)>> =
+            stream::iter(vec![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')])
+                .min_batch_sorted_by_key(4, |_: &(u8, char)| 1, |(key, _): &(u8, char)| *key)
+                .collect()
+                .await;
+        assert_eq!(sorted, vec![vec![(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]]);
+    }
+}
@@ -0,0 +1,32 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_heavy_item_is_split_into_batches() {
+        let batches: Vec<Vec<usize>> = stream::iter(vec![30])
+            .min_batch_split(10, |x| *x, |x| vec![x / 3; 3])
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![10], vec![10], vec![10]]);
+    }
+
+    #[tokio::test]
+    async fn test_pieces_are_batched_before_following_items() {
+        // 12 splits into halves of 6, 6 is split again into 3s, the light items keep batching
+        let batches: Vec<Vec<usize>> = stream::iter(vec![2, 12, 1, 4])
+            .min_batch_split(5, |x| *x, |x| vec![x / 2; 2])
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![2, 3], vec![3, 3], vec![3, 1, 4]]);
+    }
+    #[tokio::test]
+    async fn test_item_split_into_no_pieces_is_dropped() {
+        let batches: Vec<Vec<usize>> = stream::iter(vec![2, 12, 3])
+            .min_batch_split(5, |x| *x, |_| Vec::new())
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![2, 3]]);
+    }
+}
@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_tags_match_count_fn() {
+        let count_fn = |s: &&str| s.len();
+        let batches: Vec<Vec<(&str, usize)>> = stream::iter(vec!["a", "bb", "ccc", "dddd", "e"])
+            .min_batch_tagged(3, count_fn)
+            .collect()
+            .await;
+        assert_eq!(
+            batches,
+            vec![
+                vec![("a", 1), ("bb", 2)],
+                vec![("ccc", 3)],
+                vec![("dddd", 4)],
+                vec![("e", 1)]
+            ]
+        );
+        for batch in &batches {
+            for (item, weight) in batch {
+                assert_eq!(count_fn(item), *weight);
+            }
+        }
+        let total: usize = batches.iter().flatten().map(|(_, weight)| weight).sum();
+        assert_eq!(total, 11);
+    }
+}
@@ -0,0 +1,24 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_stops_before_overshooting_item() {
+        // a third item would weigh 12, more than 10 + 1, so batches stop at 8
+        let batches: Vec<Vec<usize>> = stream::iter(vec![4; 6])
+            .min_batch_target(10, 1, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![4, 4], vec![4, 4], vec![4, 4]]);
+    }
+
+    #[tokio::test]
+    async fn test_flushes_within_tolerance_band() {
+        let batches: Vec<Vec<usize>> = stream::iter(vec![5, 4, 3, 3, 3, 20, 1])
+            .min_batch_target(10, 1, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![5, 4], vec![3, 3, 3], vec![20], vec![1]]);
+    }
+}
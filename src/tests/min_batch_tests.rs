@@ -1,8 +1,12 @@
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use futures::{stream, StreamExt};
+    use crate::ext::MinBatchExt;
+    use futures::stream::FusedStream;
+    use futures::{stream, Stream, StreamExt};
     use std::collections::VecDeque;
+    use std::task::Poll;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
 
     #[tokio::test]
     async fn test_batch_stream_of_vectors() {
@@ -150,7 +154,7 @@ mod tests {
                 name: queue.pop_front().unwrap(),
                 txs_count: if x >= 4 { 1 } else { x },
             })
-            .min_batch_with_size(3, |block: &BlockOfTxs| block.txs_count)
+            .min_batch_with_weight(3, |block: &BlockOfTxs| block.txs_count)
             .collect()
             .await;
 
@@ -217,4 +221,667 @@ mod tests {
             )
         );
     }
+
+    #[tokio::test]
+    async fn test_min_batch_capped_by_len() {
+        let batches: Vec<Vec<usize>> = stream::iter(0..10)
+            .min_batch_capped(100, 3, |_: &usize| 1)
+            .collect()
+            .await;
+
+        // weights are all 1 so the cap is hit long before the weight threshold
+        assert_eq!(
+            batches,
+            vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7, 8], vec![9]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_min_batch_capped_by_weight_and_len() {
+        let batches: Vec<Vec<usize>> = stream::iter(vec![1, 5, 1, 1, 1, 1, 2, 2])
+            .min_batch_capped(4, 3, |x: &usize| *x)
+            .collect()
+            .await;
+
+        // flushed by weight, then by length, then by both on the same item
+        assert_eq!(batches, vec![vec![1, 5], vec![1, 1, 1], vec![1, 2, 2]]);
+    }
+
+    #[tokio::test]
+    async fn test_min_batch_with_weight_capped() {
+        let batches: Vec<(Vec<usize>, usize)> = stream::iter(vec![1, 1, 10, 2, 2, 2])
+            .min_batch_with_weight_capped(3, 5, |x: &usize| *x)
+            .collect()
+            .await;
+
+        // the heavy item would exceed the cap so the light batch is flushed first,
+        // then the heavy item alone exceeds the cap and is yielded on its own
+        assert_eq!(
+            batches,
+            vec![
+                (vec![1, 1], 2),
+                (vec![10], 10),
+                (vec![2, 2], 4),
+                (vec![2], 2)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_size_hint() {
+        let mut batches = stream::iter(vec![1, 1, 1, 5]).min_batch(3, |x: &usize| *x);
+        assert_eq!(batches.size_hint(), (0, Some(4)));
+        assert_eq!(batches.next().await, Some(vec![1, 1, 1]));
+        assert_eq!(batches.size_hint(), (0, Some(1)));
+        assert_eq!(batches.next().await, Some(vec![5]));
+        assert_eq!(batches.size_hint(), (0, Some(0)));
+
+        // the heavy item is buffered for the next batch and counts as one more batch
+        let mut batches =
+            stream::iter(vec![1, 1, 10, 2]).min_batch_with_weight_capped(3, 5, |x: &usize| *x);
+        assert_eq!(batches.size_hint(), (0, Some(4)));
+        assert_eq!(batches.next().await, Some((vec![1, 1], 2)));
+        assert_eq!(batches.size_hint(), (0, Some(2)));
+    }
+
+    #[tokio::test]
+    async fn test_min_batch_with_u64_weight() {
+        let bytes = u32::MAX as u64;
+        let batches: Vec<(Vec<u64>, u64)> = stream::iter(vec![bytes, bytes, 3 * bytes, 1])
+            .min_batch_with_weight(2 * bytes + 1, |x: &u64| *x)
+            .collect()
+            .await;
+
+        assert_eq!(
+            batches,
+            vec![(vec![bytes, bytes, 3 * bytes], 5 * bytes), (vec![1], 1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pending_batch_accessors() {
+        let mut batches = stream::iter(vec![1, 2])
+            .chain(stream::pending())
+            .min_batch(5, |x: &usize| *x);
+        assert!(futures::poll!(batches.next()).is_pending());
+        assert_eq!(batches.current_weight(), 3);
+        assert_eq!(batches.pending_len(), 2);
+
+        let mut batches = stream::iter(vec![1, 2])
+            .chain(stream::pending())
+            .min_batch_with_weight(5, |x: &usize| *x);
+        assert!(futures::poll!(batches.next()).is_pending());
+        assert_eq!(batches.current_weight(), 3);
+        assert_eq!(batches.pending_len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_take_pending() {
+        let (tx, rx) = mpsc::channel(8);
+        let mut batches = ReceiverStream::new(rx).min_batch(5, |x: &usize| *x);
+        tx.send(1).await.unwrap();
+        tx.send(2).await.unwrap();
+        assert!(futures::poll!(batches.next()).is_pending());
+        assert_eq!(batches.take_pending(), Some(vec![1, 2]));
+        assert_eq!(batches.take_pending(), None);
+        assert_eq!(batches.current_weight(), 0);
+
+        tx.send(3).await.unwrap();
+        tx.send(4).await.unwrap();
+        drop(tx);
+        assert_eq!(batches.collect::<Vec<_>>().await, vec![vec![3, 4]]);
+    }
+
+    #[tokio::test]
+    async fn test_reset_discards_batch_in_progress() {
+        let (tx, rx) = mpsc::channel(8);
+        let mut batches = ReceiverStream::new(rx).min_batch(5, |x: &usize| *x);
+        tx.send(1).await.unwrap();
+        tx.send(2).await.unwrap();
+        assert!(futures::poll!(batches.next()).is_pending());
+        batches.reset();
+        assert_eq!(batches.pending_len(), 0);
+        assert_eq!(batches.current_weight(), 0);
+
+        tx.send(4).await.unwrap();
+        tx.send(1).await.unwrap();
+        tx.send(3).await.unwrap();
+        drop(tx);
+        assert_eq!(batches.collect::<Vec<_>>().await, vec![vec![4, 1], vec![3]]);
+
+        let mut weighted = stream::iter(vec![1, 2])
+            .chain(stream::pending())
+            .min_batch_with_weight(10, |x: &usize| *x);
+        assert!(futures::poll!(weighted.next()).is_pending());
+        weighted.reset();
+        assert_eq!(weighted.pending_len(), 0);
+        assert_eq!(weighted.current_weight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_take_pending_with_weight() {
+        let (tx, rx) = mpsc::channel(8);
+        let mut batches = ReceiverStream::new(rx).min_batch_with_weight(5, |x: &usize| *x);
+        tx.send(1).await.unwrap();
+        tx.send(2).await.unwrap();
+        assert!(futures::poll!(batches.next()).is_pending());
+        assert_eq!(batches.take_pending(), Some((vec![1, 2], 3)));
+
+        tx.send(4).await.unwrap();
+        drop(tx);
+        assert_eq!(batches.collect::<Vec<_>>().await, vec![(vec![4], 4)]);
+    }
+
+    #[tokio::test]
+    async fn test_set_min_batch_weight_between_polls() {
+        let (tx, rx) = mpsc::channel(8);
+        let mut batches = ReceiverStream::new(rx).min_batch(10, |x: &usize| *x);
+        tx.send(2).await.unwrap();
+        tx.send(3).await.unwrap();
+        assert!(futures::poll!(batches.next()).is_pending());
+
+        // the buffered items already meet the lowered threshold and are flushed together
+        batches.set_min_batch_weight(4);
+        assert_eq!(batches.next().await, Some(vec![2, 3]));
+
+        batches.set_min_batch_weight(100);
+        tx.send(50).await.unwrap();
+        tx.send(50).await.unwrap();
+        tx.send(1).await.unwrap();
+        drop(tx);
+        assert_eq!(
+            batches.collect::<Vec<_>>().await,
+            vec![vec![50, 50], vec![1]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_min_batch_weight_with_weight() {
+        let (tx, rx) = mpsc::channel(8);
+        let mut batches = ReceiverStream::new(rx).min_batch_with_weight(10, |x: &usize| *x);
+        tx.send(2).await.unwrap();
+        tx.send(3).await.unwrap();
+        assert!(futures::poll!(batches.next()).is_pending());
+
+        batches.set_min_batch_weight(5);
+        assert_eq!(batches.next().await, Some((vec![2, 3], 5)));
+        batches.set_min_batch_weight(6);
+        tx.send(5).await.unwrap();
+        assert!(futures::poll!(batches.next()).is_pending());
+        drop(tx);
+        assert_eq!(batches.next().await, Some((vec![5], 5)));
+    }
+
+    #[tokio::test]
+    async fn test_clone_partially_consumed() {
+        let mut batches = stream::iter(1..=6).min_batch(3, |x: &usize| *x);
+        assert_eq!(batches.next().await, Some(vec![1, 2]));
+
+        let clone = batches.clone();
+        assert_eq!(batches.next().await, Some(vec![3]));
+        assert_eq!(
+            clone.collect::<Vec<_>>().await,
+            vec![vec![3], vec![4], vec![5], vec![6]]
+        );
+        assert_eq!(
+            batches.collect::<Vec<_>>().await,
+            vec![vec![4], vec![5], vec![6]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clone_with_buffered_item() {
+        let mut batches =
+            stream::iter(vec![1, 1, 10, 2]).min_batch_with_weight_capped(3, 5, |x: &usize| *x);
+        // the heavy item is buffered when cloning, so both adapters yield it
+        assert_eq!(batches.next().await, Some((vec![1, 1], 2)));
+
+        let clone = batches.clone();
+        let expected = vec![(vec![10], 10), (vec![2], 2)];
+        assert_eq!(batches.collect::<Vec<_>>().await, expected);
+        assert_eq!(clone.collect::<Vec<_>>().await, expected);
+    }
+
+    #[tokio::test]
+    async fn test_zero_min_batch_weight_yields_singletons() {
+        let batches: Vec<Vec<usize>> = stream::iter(vec![3, 0, 1])
+            .min_batch(0, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![3], vec![0], vec![1]]);
+
+        // zero weight items with a zero threshold still make singleton batches, never empty ones
+        let batches: Vec<Vec<usize>> = stream::iter(vec![1, 2, 3])
+            .min_batch(0, |_: &usize| 0)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1], vec![2], vec![3]]);
+
+        let batches: Vec<(Vec<usize>, usize)> = stream::iter(vec![1, 2])
+            .min_batch_with_weight(0, |_: &usize| 0)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![(vec![1], 0), (vec![2], 0)]);
+    }
+
+    #[tokio::test]
+    async fn test_flush_on_zero_progress() {
+        let batches: Vec<Vec<usize>> = stream::iter(0..7)
+            .min_batch(10, |_: &usize| 0)
+            .flush_on_zero_progress_after(3)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+
+        // a weighted item resets the run of zero weight items
+        let batches: Vec<Vec<usize>> = stream::iter(vec![0, 0, 1, 0, 0, 0, 9])
+            .min_batch(10, |x: &usize| *x)
+            .flush_on_zero_progress_after(3)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![0, 0, 1, 0, 0, 0], vec![9]]);
+    }
+
+    #[tokio::test]
+    async fn test_debug_shows_buffered_state() {
+        let weight_of = |x: &usize| *x;
+
+        let mut batches = stream::iter(vec![1, 2])
+            .chain(stream::pending())
+            .min_batch(5, weight_of);
+        assert!(futures::poll!(batches.next()).is_pending());
+        assert_eq!(
+            format!("{:?}", batches),
+            "MinBatch { min_batch_weight: 5, max_batch_len: None, current_batch_weight: 3, pending_len: 2, .. }"
+        );
+
+        let mut batches = stream::iter(vec![1, 2])
+            .chain(stream::pending())
+            .min_batch_with_weight(5, weight_of);
+        assert!(futures::poll!(batches.next()).is_pending());
+        assert!(format!("{:?}", batches).contains("pending_len: 2"));
+    }
+
+    #[tokio::test]
+    async fn test_polling_after_termination_keeps_returning_none() {
+        // the last partial batch is flushed by the terminal branch, later polls must not repeat it
+        let mut batches = stream::iter(vec![1, 2, 3]).min_batch(5, |x: &usize| *x);
+        assert_eq!(batches.next().await, Some(vec![1, 2, 3]));
+        for _ in 0..3 {
+            assert_eq!(futures::poll!(batches.next()), Poll::Ready(None));
+            assert!(batches.is_terminated());
+        }
+
+        let mut batches = stream::iter(vec![1, 2, 3]).min_batch_with_weight(5, |x: &usize| *x);
+        assert_eq!(batches.next().await, Some((vec![1, 2, 3], 6)));
+        for _ in 0..3 {
+            assert_eq!(futures::poll!(batches.next()), Poll::Ready(None));
+            assert!(batches.is_terminated());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_access_to_inner_stream() {
+        let mut batches = stream::iter(1..=6).min_batch(3, |_: &i32| 1);
+        assert_eq!(batches.get_ref().size_hint(), (6, Some(6)));
+        assert_eq!(batches.next().await, Some(vec![1, 2, 3]));
+        assert_eq!(batches.get_ref().size_hint(), (3, Some(3)));
+        assert_eq!(batches.get_mut().next().await, Some(4));
+        let rest: Vec<i32> = batches.into_inner().collect().await;
+        assert_eq!(rest, vec![5, 6]);
+
+        let mut batches = stream::iter(1..=4).min_batch_with_weight(3, |_: &i32| 1);
+        assert_eq!(
+            std::pin::Pin::new(&mut batches).get_pin_mut().next().await,
+            Some(1)
+        );
+        assert_eq!(batches.next().await, Some((vec![2, 3, 4], 3)));
+        assert_eq!(batches.into_inner().next().await, None);
+    }
+
+    #[cfg(feature = "debug-invariants")]
+    #[tokio::test]
+    async fn test_invariants_allow_terminal_partial_batch() {
+        // the last batch weighs 2 of 5 and must not trip the invariant checks
+        let batches: Vec<Vec<usize>> = stream::iter(vec![3, 2, 2])
+            .min_batch(5, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![3, 2], vec![2]]);
+
+        let batches: Vec<(Vec<usize>, usize)> = stream::iter(vec![3, 2, 2])
+            .min_batch_with_weight(5, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![(vec![3, 2], 5), (vec![2], 2)]);
+    }
+
+    #[tokio::test]
+    async fn test_huge_weights_saturate_instead_of_overflowing() {
+        let batches: Vec<Vec<usize>> = stream::iter(vec![1, 2])
+            .min_batch(usize::MAX, |_: &usize| usize::MAX / 2)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1, 2]]);
+
+        // a saturated batch meets even the largest threshold and is flushed
+        let batches: Vec<Vec<usize>> = stream::iter(vec![1, 2, 3, 4])
+            .min_batch(usize::MAX, |_: &usize| usize::MAX / 2 + 1)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1, 2], vec![3, 4]]);
+
+        let batches: Vec<(Vec<u8>, u8)> = stream::iter(vec![200u8, 100, 1])
+            .min_batch_with_weight(u8::MAX, |x: &u8| *x)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![(vec![200, 100], u8::MAX), (vec![1], 1)]);
+    }
+
+    #[tokio::test]
+    async fn test_weight_batches_preallocate_next_buffer() {
+        let input: Vec<usize> = (0..50).map(|i| i % 4).collect();
+        let batches: Vec<(Vec<usize>, usize)> = stream::iter(input.clone())
+            .min_batch_with_weight(7, |x: &usize| *x)
+            .collect()
+            .await;
+        // the reused capacity must not change how items are grouped
+        let expected: Vec<Vec<usize>> = stream::iter(input)
+            .min_batch(7, |x: &usize| *x)
+            .collect()
+            .await;
+        let (batches, weights): (Vec<Vec<usize>>, Vec<usize>) = batches.into_iter().unzip();
+        assert_eq!(batches, expected);
+        for (batch, weight) in batches.iter().zip(weights) {
+            assert_eq!(batch.iter().sum::<usize>(), weight);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stateful_count_fn() {
+        // every item weighs its arrival order
+        let mut arrived = 0;
+        let batches: Vec<Vec<char>> = stream::iter(vec!['a', 'b', 'c', 'd'])
+            .min_batch(3, |_: &char| {
+                arrived += 1;
+                arrived
+            })
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec!['a', 'b'], vec!['c'], vec!['d']]);
+
+        let mut arrived = 0u64;
+        let batches: Vec<(Vec<char>, u64)> = stream::iter(vec!['a', 'b', 'c', 'd'])
+            .min_batch_with_weight(3, move |_: &char| {
+                arrived += 1;
+                arrived
+            })
+            .collect()
+            .await;
+        assert_eq!(
+            batches,
+            vec![(vec!['a', 'b'], 3), (vec!['c'], 3), (vec!['d'], 4)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_min_batch_bytes() {
+        let chunks: Vec<Vec<u8>> = vec![vec![0; 3], vec![1; 4], vec![2; 8], vec![3; 1], vec![4; 2]];
+        let batches: Vec<Vec<Vec<u8>>> = stream::iter(chunks.clone())
+            .min_batch_bytes(6)
+            .collect()
+            .await;
+        assert_eq!(
+            batches,
+            vec![
+                chunks[0..2].to_vec(),
+                chunks[2..3].to_vec(),
+                chunks[3..5].to_vec()
+            ]
+        );
+        let (last, full) = batches.split_last().unwrap();
+        for batch in full {
+            assert!(batch.iter().map(Vec::len).sum::<usize>() >= 6);
+        }
+        assert_eq!(last.iter().map(Vec::len).sum::<usize>(), 3);
+
+        // anything exposing its bytes works, e.g. string slices
+        let batches: Vec<Vec<&str>> = stream::iter(vec!["ab", "cd", "efg"])
+            .min_batch_bytes(4)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec!["ab", "cd"], vec!["efg"]]);
+    }
+
+    #[tokio::test]
+    async fn test_buffered_progress_survives_pending() {
+        let mut polls = 0;
+        let source = stream::poll_fn(move |cx| {
+            polls += 1;
+            match polls {
+                1 => Poll::Ready(Some(1)),
+                2 => Poll::Ready(Some(2)),
+                3 => {
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+                4 => Poll::Ready(Some(3)),
+                _ => Poll::Ready(None),
+            }
+        });
+        let mut batches = source.min_batch(6, |x: &usize| *x);
+
+        assert_eq!(futures::poll!(batches.next()), Poll::Pending);
+        assert_eq!(batches.pending_len(), 2);
+        assert_eq!(batches.current_weight(), 3);
+
+        assert_eq!(batches.next().await, Some(vec![1, 2, 3]));
+        assert_eq!(batches.next().await, None);
+    }
+
+    /// An in-memory connection, incoming items are read as a stream and outgoing ones are
+    /// recorded by the sink half.
+    struct Duplex {
+        incoming: VecDeque<usize>,
+        sent: Vec<&'static str>,
+    }
+
+    impl Stream for Duplex {
+        type Item = usize;
+
+        fn poll_next(
+            mut self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+        ) -> Poll<Option<usize>> {
+            Poll::Ready(self.incoming.pop_front())
+        }
+    }
+
+    impl futures::Sink<&'static str> for Duplex {
+        type Error = std::convert::Infallible;
+
+        fn poll_ready(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn start_send(
+            mut self: std::pin::Pin<&mut Self>,
+            item: &'static str,
+        ) -> Result<(), Self::Error> {
+            self.sent.push(item);
+            Ok(())
+        }
+
+        fn poll_flush(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(
+            self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+        ) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sink_half_is_forwarded() {
+        use futures::SinkExt;
+
+        let duplex = || Duplex {
+            incoming: VecDeque::from(vec![1, 2, 3, 4, 5]),
+            sent: Vec::new(),
+        };
+
+        let mut batched = duplex().min_batch(2, |_: &usize| 1);
+        assert_eq!(batched.next().await, Some(vec![1, 2]));
+        batched.send("ack 1").await.unwrap();
+        assert_eq!(batched.next().await, Some(vec![3, 4]));
+        batched.send("ack 2").await.unwrap();
+        assert_eq!(batched.next().await, Some(vec![5]));
+        batched.close().await.unwrap();
+        assert_eq!(batched.get_ref().sent, vec!["ack 1", "ack 2"]);
+
+        let mut weighted = duplex().min_batch_with_weight(5, |x: &usize| *x);
+        assert_eq!(weighted.next().await, Some((vec![1, 2, 3], 6)));
+        weighted.send("ack").await.unwrap();
+        assert_eq!(weighted.get_ref().sent, vec!["ack"]);
+    }
+
+    async fn take_first_batch<S: Stream<Item = Vec<i32>> + Unpin>(batches: S) -> Vec<Vec<i32>> {
+        batches.take(1).collect().await
+    }
+
+    #[tokio::test]
+    async fn test_by_ref_loses_no_batches() {
+        // the adapter is `Unpin` whenever the inner stream is, so `&mut` reborrows directly
+        let mut batched = stream::iter(1..=7).min_batch(2, |_: &i32| 1);
+        let first: Vec<Vec<i32>> = batched.by_ref().take(1).collect().await;
+        assert_eq!(first, vec![vec![1, 2]]);
+        assert_eq!(take_first_batch(&mut batched).await, vec![vec![3, 4]]);
+        let rest: Vec<Vec<i32>> = batched.collect().await;
+        assert_eq!(rest, vec![vec![5, 6], vec![7]]);
+
+        // a `!Unpin` inner stream has to be pinned first, then `Pin<&mut _>` reborrows
+        let not_unpin = stream::iter(1..=5).then(|x| async move { x });
+        let mut batched = std::pin::pin!(not_unpin.min_batch(2, |_: &i32| 1));
+        assert_eq!(take_first_batch(batched.as_mut()).await, vec![vec![1, 2]]);
+        let rest: Vec<Vec<i32>> = batched.collect().await;
+        assert_eq!(rest, vec![vec![3, 4], vec![5]]);
+    }
+
+    #[tokio::test]
+    async fn test_single_heavy_item_is_yielded_alone() {
+        let batches: Vec<Vec<usize>> = stream::once(futures::future::ready(10))
+            .min_batch(3, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![10]]);
+
+        // exactly meeting the threshold flushes as well
+        let mut batched = stream::once(futures::future::ready(3))
+            .chain(stream::pending())
+            .min_batch(3, |x: &usize| *x);
+        assert_eq!(batched.next().await, Some(vec![3]));
+    }
+
+    #[tokio::test]
+    async fn test_drop_incomplete_tail() {
+        let batches: Vec<Vec<usize>> = stream::iter(1..=7)
+            .min_batch(3, |_: &usize| 1)
+            .drop_incomplete_tail()
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        // a total weight that is a multiple of the threshold loses nothing
+        let batches: Vec<Vec<usize>> = stream::iter(1..=6)
+            .min_batch(3, |_: &usize| 1)
+            .drop_incomplete_tail()
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    }
+
+    #[tokio::test]
+    async fn test_eager_flush_on_pending() {
+        let mut polls = 0;
+        let source = stream::poll_fn(move |cx| {
+            polls += 1;
+            match polls {
+                1 => Poll::Ready(Some(1)),
+                2 => {
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+                3 => Poll::Ready(Some(2)),
+                4 => Poll::Ready(Some(3)),
+                _ => Poll::Ready(None),
+            }
+        });
+        let mut batches = source.min_batch(6, |x: &usize| *x).eager_flush();
+
+        assert_eq!(batches.next().await, Some(vec![1]));
+        assert_eq!(batches.next().await, Some(vec![2, 3]));
+        assert_eq!(batches.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_with_weight_batches_like_min_batch() {
+        let input: Vec<usize> = vec![0, 3, 0, 0, 1, 9, 2, 2, 0, 5, 1, 1, 4, 0];
+        for min_batch_weight in 0..12 {
+            let batches: Vec<Vec<usize>> = stream::iter(input.clone())
+                .min_batch(min_batch_weight, |x: &usize| *x)
+                .collect()
+                .await;
+            let weighed: Vec<(Vec<usize>, usize)> = stream::iter(input.clone())
+                .min_batch_with_weight(min_batch_weight, |x: &usize| *x)
+                .collect()
+                .await;
+            let (weighed, weights): (Vec<Vec<usize>>, Vec<usize>) = weighed.into_iter().unzip();
+            assert_eq!(batches, weighed, "min_batch_weight {min_batch_weight}");
+            for (batch, weight) in batches.iter().zip(weights) {
+                assert_eq!(batch.iter().sum::<usize>(), weight);
+            }
+        }
+
+        let mut plain = stream::iter(input.clone()).min_batch(6, |x: &usize| *x);
+        let mut weighed = stream::iter(input).min_batch_with_weight(6, |x: &usize| *x);
+        assert_eq!(
+            plain.next().await,
+            weighed.next().await.map(|(batch, _)| batch)
+        );
+        assert_eq!(
+            plain.take_pending(),
+            weighed.take_pending().map(|(batch, _)| batch)
+        );
+        assert_eq!(plain.current_weight(), weighed.current_weight());
+        loop {
+            let next = plain.next().await;
+            assert_eq!(next, weighed.next().await.map(|(batch, _)| batch));
+            if next.is_none() {
+                break;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_box_stream_batches_like_concrete_adapter() {
+        fn batched(input: Vec<usize>) -> futures::stream::BoxStream<'static, Vec<usize>> {
+            stream::iter(input).min_batch_box_stream(5, |x: &usize| *x)
+        }
+        let input = vec![1, 4, 2, 2, 0, 7, 1, 1];
+        let boxed: Vec<Vec<usize>> = batched(input.clone()).collect().await;
+        let concrete: Vec<Vec<usize>> = stream::iter(input)
+            .min_batch(5, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(boxed, concrete);
+    }
 }
@@ -0,0 +1,21 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_matches_explicit_map() {
+        let input = vec![1, 2, 3, 1, 1, 5, 2];
+        let chained: Vec<usize> = stream::iter(input.clone())
+            .min_batch(3, |x: &usize| *x)
+            .map(|batch| batch.iter().sum::<usize>())
+            .collect()
+            .await;
+        let fused: Vec<usize> = stream::iter(input)
+            .min_batch_then_map(3, |x: &usize| *x, |batch| batch.iter().sum::<usize>())
+            .collect()
+            .await;
+        assert_eq!(fused, vec![3, 3, 7, 2]);
+        assert_eq!(fused, chained);
+    }
+}
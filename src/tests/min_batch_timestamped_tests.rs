@@ -0,0 +1,24 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use std::time::Duration;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_timestamps_follow_flushes() {
+        let delayed = stream::iter(1..=4).then(|x| async move {
+            if x == 3 {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+            x
+        });
+        let batches: Vec<(Vec<i32>, tokio::time::Instant)> = delayed
+            .min_batch_timestamped(2, |_: &i32| 1)
+            .collect()
+            .await;
+
+        assert_eq!(batches[0].0, vec![1, 2]);
+        assert_eq!(batches[1].0, vec![3, 4]);
+        assert!(batches[1].1 - batches[0].1 >= Duration::from_millis(50));
+    }
+}
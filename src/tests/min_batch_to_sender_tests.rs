@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::stream;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn test_forwards_batches_under_backpressure() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let receiver = tokio::spawn(async move {
+            let mut received = Vec::new();
+            while let Some(batch) = rx.recv().await {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                received.push(batch);
+            }
+            received
+        });
+        stream::iter(1..=7)
+            .min_batch_to_sender(2, |_: &i32| 1, tx)
+            .await
+            .unwrap();
+        assert_eq!(
+            receiver.await.unwrap(),
+            vec![vec![1, 2], vec![3, 4], vec![5, 6], vec![7]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stops_once_receiver_is_dropped() {
+        let (tx, rx) = mpsc::channel(1);
+        drop(rx);
+        let result = stream::iter(1..=4)
+            .min_batch_to_sender(2, |_: &i32| 1, tx)
+            .await;
+        assert_eq!(result.unwrap_err().0, vec![1, 2]);
+    }
+}
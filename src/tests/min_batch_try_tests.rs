@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    fn parse_weight(s: &&str) -> Result<usize, String> {
+        s.parse::<usize>()
+            .map_err(|_| format!("malformed weight {s}"))
+    }
+
+    #[tokio::test]
+    async fn test_error_flushes_pending_batch_then_continues() {
+        let batches: Vec<Result<Vec<&str>, String>> = stream::iter(vec!["1", "x", "2", "2", "5"])
+            .min_batch_try(3, parse_weight)
+            .collect()
+            .await;
+
+        assert_eq!(
+            batches,
+            vec![
+                Ok(vec!["1"]),
+                Err("malformed weight x".to_string()),
+                Ok(vec!["2", "2"]),
+                Ok(vec!["5"]),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_error_with_empty_batch() {
+        let batches: Vec<Result<Vec<&str>, String>> = stream::iter(vec!["x", "3", "y"])
+            .min_batch_try(3, parse_weight)
+            .collect()
+            .await;
+
+        assert_eq!(
+            batches,
+            vec![
+                Err("malformed weight x".to_string()),
+                Ok(vec!["3"]),
+                Err("malformed weight y".to_string()),
+            ]
+        );
+    }
+}
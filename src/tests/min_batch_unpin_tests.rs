@@ -0,0 +1,31 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_same_batches_as_pinned_version() {
+        let weights = vec![3, 0, 1, 7, 2, 2, 0, 5, 1, 1, 4];
+        for min_batch_weight in 0..10 {
+            let pinned: Vec<Vec<usize>> = stream::iter(weights.clone())
+                .min_batch(min_batch_weight, |w: &usize| *w)
+                .collect()
+                .await;
+            let unpinned: Vec<Vec<usize>> = stream::iter(weights.clone())
+                .min_batch_unpin(min_batch_weight, |w: &usize| *w)
+                .collect()
+                .await;
+            assert_eq!(pinned, unpinned);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_mut_between_polls() {
+        let mut batches = stream::iter(1..=6).min_batch_unpin(2, |_: &i32| 1);
+        assert_eq!(batches.next().await, Some(vec![1, 2]));
+        assert_eq!(batches.get_mut().next().await, Some(3));
+        assert_eq!(batches.next().await, Some(vec![4, 5]));
+        assert_eq!(batches.next().await, Some(vec![6]));
+        assert_eq!(batches.next().await, None);
+    }
+}
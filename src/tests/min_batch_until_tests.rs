@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_predicate_flushes_before_weight() {
+        let events = vec!["start", "a", "end", "b", "c", "d", "e", "f"];
+        // flush once a batch holds both markers, or at 4 items
+        let batches: Vec<Vec<&str>> = stream::iter(events)
+            .min_batch_until(
+                4,
+                |_: &&str| 1,
+                |items: &[&str]| items.contains(&"start") && items.contains(&"end"),
+            )
+            .collect()
+            .await;
+        assert_eq!(
+            batches,
+            vec![
+                vec!["start", "a", "end"],
+                vec!["b", "c", "d", "e"],
+                vec!["f"]
+            ]
+        );
+    }
+}
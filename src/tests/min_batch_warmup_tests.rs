@@ -0,0 +1,17 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_first_batch_flushes_at_first_weight() {
+        let batches: Vec<Vec<usize>> = stream::iter(vec![1, 1, 2, 1, 3, 2, 2, 1, 4, 1])
+            .min_batch_warmup(2, 5, |n: &usize| *n)
+            .collect()
+            .await;
+        assert_eq!(
+            batches,
+            vec![vec![1, 1], vec![2, 1, 3], vec![2, 2, 1], vec![4, 1]]
+        );
+    }
+}
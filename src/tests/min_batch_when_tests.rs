@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Msg {
+        bytes: usize,
+        priority: bool,
+    }
+
+    fn msg(bytes: usize, priority: bool) -> Msg {
+        Msg { bytes, priority }
+    }
+
+    #[tokio::test]
+    async fn test_composite_predicate() {
+        let msgs = vec![
+            msg(1, false),
+            msg(1, false),
+            msg(1, false),
+            msg(1, true),
+            msg(8, false),
+            msg(3, false),
+            msg(1, false),
+        ];
+        // flush by bytes, by count or as soon as a priority message is buffered
+        let batches: Vec<Vec<Msg>> = stream::iter(msgs.clone())
+            .min_batch_when(
+                |m: &Msg| m.bytes,
+                |items: &[Msg], bytes| {
+                    bytes >= 8 || items.len() >= 3 || items.iter().any(|m| m.priority)
+                },
+            )
+            .collect()
+            .await;
+        assert_eq!(
+            batches,
+            vec![
+                msgs[0..3].to_vec(),
+                msgs[3..4].to_vec(),
+                msgs[4..5].to_vec(),
+                msgs[5..7].to_vec()
+            ]
+        );
+    }
+}
@@ -0,0 +1,24 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_barriers_force_flushes_under_weight() {
+        // every third item is a barrier, the weight threshold is never reached on its own
+        let batches: Vec<Vec<usize>> = stream::iter(1..=8)
+            .min_batch_with_barriers(10, |_: &usize| 1, |x: &usize| x.is_multiple_of(3))
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8]]);
+    }
+
+    #[tokio::test]
+    async fn test_weight_still_flushes_between_barriers() {
+        let batches: Vec<Vec<usize>> = stream::iter(vec![2, 2, 0, 1, 5, 1])
+            .min_batch_with_barriers(4, |x: &usize| *x, |x: &usize| *x == 0)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![2, 2], vec![0], vec![1, 5], vec![1]]);
+    }
+}
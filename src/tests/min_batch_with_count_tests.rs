@@ -0,0 +1,20 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_count_matches_batch_len() {
+        let batches: Vec<(Vec<usize>, usize)> = stream::iter(vec![1, 1, 3, 5, 2, 1, 1])
+            .min_batch_with_count(4, |x: &usize| *x)
+            .collect()
+            .await;
+        assert_eq!(
+            batches,
+            vec![(vec![1, 1, 3], 3), (vec![5], 1), (vec![2, 1, 1], 3)]
+        );
+        for (batch, count) in &batches {
+            assert_eq!(batch.len(), *count);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use std::cell::RefCell;
+
+    #[tokio::test]
+    async fn test_buffered_items_are_handed_over_on_drop() {
+        let dropped = RefCell::new(Vec::new());
+        let mut batches = stream::iter(1..=5)
+            .chain(stream::pending())
+            .min_batch_with_drop_handler(
+                3,
+                |_: &i32| 1,
+                |items| dropped.borrow_mut().extend(items),
+            );
+        assert_eq!(batches.next().await, Some(vec![1, 2, 3]));
+        assert!(futures::poll!(batches.next()).is_pending());
+        drop(batches);
+        assert_eq!(dropped.into_inner(), vec![4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_not_called_once_drained() {
+        let called = RefCell::new(false);
+        let batches: Vec<Vec<i32>> = stream::iter(1..=5)
+            .min_batch_with_drop_handler(3, |_: &i32| 1, |_| *called.borrow_mut() = true)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1, 2, 3], vec![4, 5]]);
+        assert!(!called.into_inner());
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::StreamExt;
+    use std::pin::pin;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+    use tokio::time::Instant;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_heartbeats_on_idle_stream() {
+        let (tx, rx) = mpsc::channel(8);
+        let start = Instant::now();
+        let mut batches = pin!(ReceiverStream::new(rx).min_batch_with_heartbeat(
+            3,
+            |x: &usize| *x,
+            Duration::from_millis(100),
+        ));
+
+        assert_eq!(batches.next().await, Some(vec![]));
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+        assert_eq!(batches.next().await, Some(vec![]));
+        assert_eq!(start.elapsed(), Duration::from_millis(200));
+
+        // a real batch restarts the interval, so no heartbeat follows right after it
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        tx.send(3).await.unwrap();
+        assert_eq!(batches.next().await, Some(vec![3]));
+        assert_eq!(start.elapsed(), Duration::from_millis(250));
+        assert_eq!(batches.next().await, Some(vec![]));
+        assert_eq!(start.elapsed(), Duration::from_millis(350));
+
+        // heartbeats do not flush buffered items
+        tx.send(1).await.unwrap();
+        assert_eq!(batches.next().await, Some(vec![]));
+        tx.send(2).await.unwrap();
+        assert_eq!(batches.next().await, Some(vec![1, 2]));
+
+        drop(tx);
+        assert_eq!(batches.next().await, None);
+    }
+
+    #[test]
+    fn test_builds_outside_of_a_runtime() {
+        let mut batches = pin!(
+            futures::stream::iter(vec![1, 2, 3]).min_batch_with_heartbeat(
+                3,
+                |x: &usize| *x,
+                Duration::from_millis(100),
+            )
+        );
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            assert_eq!(batches.next().await, Some(vec![1, 2]));
+            assert_eq!(batches.next().await, Some(vec![3]));
+            assert_eq!(batches.next().await, None);
+        });
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_first_interval_starts_at_first_poll() {
+        let (_tx, rx) = mpsc::channel::<usize>(8);
+        let mut batches = pin!(ReceiverStream::new(rx).min_batch_with_heartbeat(
+            3,
+            |x: &usize| *x,
+            Duration::from_millis(100),
+        ));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let first_poll = Instant::now();
+        assert_eq!(batches.next().await, Some(vec![]));
+        assert_eq!(first_poll.elapsed(), Duration::from_millis(100));
+    }
+}
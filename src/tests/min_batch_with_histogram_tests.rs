@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use crate::min_batch_with_histogram::Histogram;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_histogram_counts_batch_weights() {
+        let mut batches = stream::iter(vec![3, 1, 5, 9, 4, 1]).min_batch_with_histogram(
+            3,
+            |x: &usize| *x,
+            Histogram::new(2, 4, 8),
+        );
+        while batches.next().await.is_some() {}
+
+        // weights 3, 6, 9, 5 and the terminal 1
+        assert_eq!(
+            batches.weight_histogram().buckets(),
+            &[(0..=1, 1), (2..=3, 1), (4..=5, 1), (6..=usize::MAX, 2)]
+        );
+    }
+
+    #[test]
+    fn test_histogram_only_keeps_the_window() {
+        let mut histogram = Histogram::new(10, 3, 4);
+        for weight in [1, 2, 3, 4, 15, 15, 100, 100] {
+            histogram.record(weight);
+        }
+        assert_eq!(histogram.len(), 4);
+        assert_eq!(
+            histogram.buckets(),
+            &[(0..=9, 0), (10..=19, 2), (20..=usize::MAX, 2)]
+        );
+    }
+
+    #[test]
+    fn test_saturated_weight_lands_in_the_last_bucket() {
+        let mut histogram = Histogram::new(10, 2, 4);
+        histogram.record(usize::MAX);
+        let (range, count) = histogram.buckets().last().unwrap();
+        assert!(range.contains(&usize::MAX));
+        assert_eq!(*count, 1);
+    }
+}
@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use crate::min_batch_with_stats::BatchStats;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_stats_of_mixed_weight_batches() {
+        let batches: Vec<(Vec<usize>, BatchStats)> = stream::iter(vec![3, 1, 7, 12, 2])
+            .min_batch_with_stats(10, |x: &usize| *x)
+            .collect()
+            .await;
+
+        assert_eq!(
+            batches,
+            vec![
+                (
+                    vec![3, 1, 7],
+                    BatchStats {
+                        total: 11,
+                        count: 3,
+                        min: 1,
+                        max: 7
+                    }
+                ),
+                // a single item batch has equal min and max
+                (
+                    vec![12],
+                    BatchStats {
+                        total: 12,
+                        count: 1,
+                        min: 12,
+                        max: 12
+                    }
+                ),
+                (
+                    vec![2],
+                    BatchStats {
+                        total: 2,
+                        count: 1,
+                        min: 2,
+                        max: 2
+                    }
+                )
+            ]
+        );
+    }
+}
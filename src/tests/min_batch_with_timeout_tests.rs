@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::StreamExt;
+    use std::pin::pin;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_partial_batch_flushed_after_timeout() {
+        let (tx, rx) = mpsc::channel(8);
+        let mut batches = pin!(ReceiverStream::new(rx).min_batch_with_timeout(
+            10,
+            |x: &usize| *x,
+            Duration::from_millis(100),
+        ));
+
+        tx.send(1).await.unwrap();
+        tx.send(2).await.unwrap();
+        // the stream goes quiet so the pending batch is flushed by the timer
+        assert_eq!(batches.next().await, Some(vec![1, 2]));
+
+        // nothing is buffered, so the timer must not emit an empty batch
+        let idle = tokio::time::timeout(Duration::from_secs(1), batches.next()).await;
+        assert!(idle.is_err());
+
+        // the timer is re-armed with the next item
+        tx.send(3).await.unwrap();
+        assert_eq!(batches.next().await, Some(vec![3]));
+
+        tx.send(10).await.unwrap();
+        drop(tx);
+        assert_eq!(batches.next().await, Some(vec![10]));
+        assert_eq!(batches.next().await, None);
+    }
+}
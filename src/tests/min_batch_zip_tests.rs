@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use std::time::Duration;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_batches_of_different_rates_are_aligned() {
+        let slow = stream::unfold('a', |c| async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            (c <= 'g').then(|| (c, (c as u8 + 1) as char))
+        });
+        let pairs: Vec<(Vec<i32>, Vec<char>)> = stream::iter(1..=8)
+            .min_batch_zip(slow, 2, |_: &i32| 1, 3, |_: &char| 1)
+            .collect()
+            .await;
+        assert_eq!(
+            pairs,
+            vec![
+                (vec![1, 2], vec!['a', 'b', 'c']),
+                (vec![3, 4], vec!['d', 'e', 'f']),
+                (vec![5, 6], vec!['g']),
+                (vec![7, 8], vec![]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,91 @@
+mod batch_sink_tests;
+#[cfg(feature = "std")]
+mod builder_tests;
+mod collect_batches_into_tests;
+#[cfg(feature = "rayon")]
+mod dispatch_rayon_tests;
+#[cfg(feature = "drop-warning")]
+mod drop_warning_tests;
+#[cfg(feature = "std")]
+mod for_each_batch_parallel_tests;
+mod mem_size_tests;
+mod min_batch_2d_tests;
+mod min_batch_absorb_tail_tests;
+mod min_batch_adaptive_tests;
+#[cfg(feature = "async-closure")]
+mod min_batch_async_tests;
+mod min_batch_async_weight_tests;
+mod min_batch_boxed_tests;
+mod min_batch_by_key_tests;
+#[cfg(feature = "std")]
+mod min_batch_cancellable_tests;
+#[cfg(feature = "std")]
+mod min_batch_catching_tests;
+mod min_batch_collect_tests;
+mod min_batch_credited_tests;
+#[cfg(feature = "std")]
+mod min_batch_deadline_tagged_tests;
+#[cfg(feature = "std")]
+mod min_batch_deadline_tests;
+mod min_batch_decayed_tests;
+#[cfg(feature = "std")]
+mod min_batch_dedup_tests;
+#[cfg(feature = "std")]
+mod min_batch_eager_pull_tests;
+mod min_batch_enumerate_tests;
+mod min_batch_exclusive_tests;
+mod min_batch_filter_tests;
+mod min_batch_fold_tests;
+#[cfg(feature = "std")]
+mod min_batch_for_cpus_tests;
+#[cfg(feature = "std")]
+mod min_batch_grouped_tests;
+mod min_batch_indexed_tests;
+mod min_batch_inspect_tests;
+mod min_batch_lookahead_tests;
+mod min_batch_nonzero_tests;
+mod min_batch_observed_tests;
+mod min_batch_on_sentinel_tests;
+#[cfg(feature = "std")]
+mod min_batch_ordered_parallel_tests;
+#[cfg(feature = "std")]
+mod min_batch_pooled_tests;
+mod min_batch_prekeyed_tests;
+mod min_batch_ranged_tests;
+#[cfg(feature = "std")]
+mod min_batch_rate_limited_tests;
+mod min_batch_ref_tests;
+#[cfg(feature = "std")]
+mod min_batch_reporting_tests;
+mod min_batch_results_tests;
+mod min_batch_shared_tests;
+mod min_batch_sliding_tests;
+mod min_batch_sorted_by_key_tests;
+mod min_batch_split_tests;
+mod min_batch_tagged_tests;
+mod min_batch_target_tests;
+mod min_batch_tests;
+mod min_batch_then_map_tests;
+#[cfg(feature = "std")]
+mod min_batch_timestamped_tests;
+#[cfg(feature = "std")]
+mod min_batch_to_sender_tests;
+mod min_batch_try_tests;
+mod min_batch_unpin_tests;
+mod min_batch_until_tests;
+mod min_batch_warmup_tests;
+mod min_batch_when_tests;
+mod min_batch_with_barriers_tests;
+mod min_batch_with_count_tests;
+mod min_batch_with_drop_handler_tests;
+#[cfg(feature = "std")]
+mod min_batch_with_heartbeat_tests;
+mod min_batch_with_histogram_tests;
+mod min_batch_with_stats_tests;
+#[cfg(feature = "std")]
+mod min_batch_with_timeout_tests;
+mod min_batch_zip_tests;
+mod rebatch_tests;
+mod sync_tests;
+mod take_batches_tests;
+mod test_util_tests;
@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+
+    #[tokio::test]
+    async fn test_small_batches_combine_and_large_ones_split() {
+        let input = vec![
+            vec![1, 2],
+            vec![3],
+            vec![4, 5, 6, 7, 8, 9, 10],
+            vec![],
+            vec![11],
+        ];
+        let batches: Vec<Vec<i32>> = stream::iter(input.clone())
+            .rebatch(3, |_: &i32| 1)
+            .collect()
+            .await;
+        assert_eq!(
+            batches,
+            vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9], vec![10, 11]]
+        );
+
+        let flattened: Vec<Vec<i32>> = stream::iter(input.into_iter().flatten())
+            .min_batch(3, |_: &i32| 1)
+            .collect()
+            .await;
+        assert_eq!(batches, flattened);
+    }
+}
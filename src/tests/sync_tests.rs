@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use crate::split_by_weight;
+    use crate::sync::MinBatchIterExt;
+    use futures::{stream, StreamExt};
+    use std::collections::VecDeque;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct BlockOfTxs {
+        name: char,
+        txs_count: usize,
+    }
+
+    #[test]
+    fn test_batch_iter_of_blocks() {
+        let block_names: Vec<char> = vec!['a', 'b', 'c', 'd'];
+        let batches: Vec<Vec<BlockOfTxs>> = (1..=4)
+            .map(|x| BlockOfTxs {
+                name: block_names[x - 1],
+                txs_count: x,
+            })
+            .min_batch(3, |block: &BlockOfTxs| block.txs_count)
+            .collect();
+
+        assert_eq!(batches.len(), 3);
+        assert_eq!(
+            batches[0],
+            vec![
+                BlockOfTxs {
+                    name: 'a',
+                    txs_count: 1
+                },
+                BlockOfTxs {
+                    name: 'b',
+                    txs_count: 2
+                }
+            ],
+        );
+        assert_eq!(
+            batches[1],
+            vec![BlockOfTxs {
+                name: 'c',
+                txs_count: 3
+            }],
+        );
+        assert_eq!(
+            batches[2],
+            vec![BlockOfTxs {
+                name: 'd',
+                txs_count: 4
+            }],
+        );
+    }
+
+    #[test]
+    fn test_batch_iter_of_vectors() {
+        let mut queue: VecDeque<char> = ('a'..='z').collect();
+
+        let batches: Vec<Vec<Vec<char>>> = (1..=5)
+            .map(|x| {
+                (0..x)
+                    .map(|_| queue.pop_front().unwrap())
+                    .collect::<Vec<char>>()
+            })
+            .min_batch(3, |xs: &Vec<char>| xs.len())
+            .collect();
+
+        assert_eq!(batches.len(), 4);
+        assert_eq!(batches[0], vec![vec!['a'], vec!['b', 'c']]);
+        assert_eq!(batches[1], vec![vec!['d', 'e', 'f']]);
+        assert_eq!(batches[2], vec![vec!['g', 'h', 'i', 'j']]);
+    }
+
+    #[test]
+    fn test_batch_iter_short() {
+        let batches: Vec<Vec<usize>> = (1..=1).min_batch(3, |x: &usize| *x).collect();
+        assert_eq!(batches, vec![vec![1]]);
+        let batches: Vec<Vec<usize>> = (1..1).min_batch(3, |x: &usize| *x).collect();
+        assert!(batches.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_split_by_weight_like_async_adapter() {
+        let block_names: Vec<char> = vec!['a', 'b', 'c', 'd'];
+        let blocks: Vec<BlockOfTxs> = (1..=4)
+            .map(|x| BlockOfTxs {
+                name: block_names[x - 1],
+                txs_count: x,
+            })
+            .collect();
+
+        let batches = split_by_weight(blocks.clone(), 3, |block: &BlockOfTxs| block.txs_count);
+        let expected: Vec<Vec<BlockOfTxs>> = stream::iter(blocks)
+            .min_batch(3, |block: &BlockOfTxs| block.txs_count)
+            .collect()
+            .await;
+        assert_eq!(batches, expected);
+        assert_eq!(
+            batches.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![2, 1, 1]
+        );
+        assert!(split_by_weight(Vec::new(), 3, |x: &usize| *x).is_empty());
+    }
+}
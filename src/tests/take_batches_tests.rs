@@ -0,0 +1,32 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use futures::{stream, StreamExt};
+    use std::time::Duration;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_two_full_batches_plus_buffered_remainder() {
+        // the first 7 items are ready at once, the following ones trickle in
+        let long = stream::iter(1..=7)
+            .chain(stream::unfold(8, |n| async move {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                (n <= 100).then_some((n, n + 1))
+            }))
+            .boxed();
+        let mut batches = long.take_batches(3, |_: &i32| 1, 2);
+        assert_eq!(batches.next().await, Some(vec![1, 2, 3]));
+        assert_eq!(batches.next().await, Some(vec![4, 5, 6]));
+        assert_eq!(batches.next().await, Some(vec![7]));
+        assert_eq!(batches.next().await, None);
+        assert_eq!(batches.into_inner().next().await, Some(8));
+    }
+
+    #[tokio::test]
+    async fn test_short_stream_ends_early() {
+        let batches: Vec<Vec<i32>> = stream::iter(1..=4)
+            .take_batches(3, |_: &i32| 1, 2)
+            .collect()
+            .await;
+        assert_eq!(batches, vec![vec![1, 2, 3], vec![4]]);
+    }
+}
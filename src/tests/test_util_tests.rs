@@ -0,0 +1,26 @@
+#[cfg(test)]
+mod tests {
+    use crate::ext::MinBatchExt;
+    use crate::test_util::collect_now;
+    use futures::{stream, StreamExt};
+
+    #[test]
+    fn test_collect_now_drains_ready_batches() {
+        let mut batches = stream::iter(1..=7).min_batch(3, |_: &i32| 1);
+        assert_eq!(
+            collect_now(&mut batches),
+            vec![vec![1, 2, 3], vec![4, 5, 6], vec![7]]
+        );
+        assert!(collect_now(&mut batches).is_empty());
+    }
+
+    #[test]
+    fn test_collect_now_stops_at_pending() {
+        let mut batches = stream::iter(1..=4)
+            .chain(stream::pending())
+            .min_batch(3, |_: &i32| 1);
+        // the partial batch of 4 stays buffered while the source is pending
+        assert_eq!(collect_now(&mut batches), vec![vec![1, 2, 3]]);
+        assert_eq!(batches.pending_len(), 1);
+    }
+}
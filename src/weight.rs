@@ -0,0 +1,38 @@
+use core::ops::Add;
+
+/// A weight accumulated by [`MinBatchWithWeight`](crate::min_batch_with_weight::MinBatchWithWeight).
+/// `Default` is used as the zero of an empty batch.
+///
+/// Weights are accumulated with [`Weight::saturating_add`], so a batch whose weight saturates at
+/// the maximum of its type meets any threshold and is flushed instead of overflowing. It is
+/// implemented for all the primitive numeric types, floats simply add up as they saturate to
+/// infinity on their own.
+pub trait Weight: Copy + Default + Add<Output = Self> + PartialOrd {
+    fn saturating_add(self, rhs: Self) -> Self;
+}
+
+macro_rules! saturating_weight {
+    ($($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                fn saturating_add(self, rhs: Self) -> Self {
+                    <$t>::saturating_add(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+saturating_weight!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Weight for f32 {
+    fn saturating_add(self, rhs: Self) -> Self {
+        self + rhs
+    }
+}
+
+impl Weight for f64 {
+    fn saturating_add(self, rhs: Self) -> Self {
+        self + rhs
+    }
+}
@@ -0,0 +1,60 @@
+//! Lives in its own test binary, as the counting allocator replaces the global allocator of the
+//! whole binary it is linked into.
+#![cfg(feature = "std")]
+
+use futures::stream;
+use futures::task::noop_waker_ref;
+use futures::StreamExt;
+use min_batch::ext::MinBatchExt;
+use min_batch::min_batch_pooled::BatchPool;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::task::{Context, Poll};
+
+/// Counts the allocations of the current thread, so that the threads of the test harness do not
+/// disturb the count.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[test]
+fn test_no_allocations_after_warmup() {
+    let items: Vec<u64> = (0..10_000).collect();
+    let pool = BatchPool::new(16);
+    let mut adapter = stream::iter(items).min_batch_pooled(16, |_: &u64| 1, pool);
+    let mut cx = Context::from_waker(noop_waker_ref());
+    let mut poll_batch = |cx: &mut Context<'_>| match adapter.poll_next_unpin(cx) {
+        Poll::Ready(Some(batch)) => batch.len(),
+        _ => panic!("expected a batch"),
+    };
+
+    // the first batches allocate the buffer and the free list of the pool
+    for _ in 0..4 {
+        assert_eq!(poll_batch(&mut cx), 16);
+    }
+    let warm = allocations();
+    for _ in 0..100 {
+        assert_eq!(poll_batch(&mut cx), 16);
+    }
+    assert_eq!(allocations(), warm);
+}